@@ -204,6 +204,46 @@ fn do_bench_pop<Q: PriorityQueue<Item: Ord + Clone>>(c: &mut BenchmarkGroup<Wall
     });
 }
 
+fn bench_push_pop(c: &mut Criterion) {
+    call_push_pop_group(c, 100);
+    call_push_pop_group(c, 1000);
+    call_push_pop_group(c, 10000);
+}
+
+/// Interleaves pushes and pops on a full queue, so the time is dominated by the sift loops.
+fn call_push_pop_group(c: &mut Criterion, n: i64) {
+    let mut group = c.benchmark_group(format!("Push & pop {n} i64 items on {n}"));
+    group.sample_size(30);
+
+    let mut rng = SmallRng::seed_from_u64(SEED);
+    let mut items: Vec<i64> = (0..2 * n).collect();
+    items.shuffle(&mut rng);
+    let (initial, items) = items.split_at(n as usize);
+
+    do_bench_push_pop::<Beap<i64>>(&mut group, Beap::from(initial.to_vec()), items);
+    do_bench_push_pop::<BinaryHeap<i64>>(&mut group, BinaryHeap::from(initial.to_vec()), items);
+
+    group.finish();
+}
+
+fn do_bench_push_pop<Q: PriorityQueue<Item: Ord + Clone>>(
+    c: &mut BenchmarkGroup<WallTime>,
+    q: Q,
+    items: &[Q::Item],
+) {
+    c.bench_function(Q::describe(), |b| {
+        b.iter(|| {
+            let mut queue = q.clone();
+            let mut popped = Vec::with_capacity(items.len());
+            for i in items {
+                queue.push(i.clone());
+                popped.push(queue.pop());
+            }
+            black_box((queue, popped))
+        })
+    });
+}
+
 fn bench_push_peek(c: &mut Criterion) {
     call_push_peek_group(c, 100);
     call_push_peek_group(c, 1000);
@@ -321,12 +361,112 @@ fn do_bench_push_tail<Q: PriorityQueue<Item: Ord + Clone>>(
     });
 }
 
+fn bench_from_vec(c: &mut Criterion) {
+    call_from_vec_group(c, 10000);
+    call_from_vec_group(c, 1000000);
+}
+
+/// The parallel path is only measured with `--features rayon`.
+fn call_from_vec_group(c: &mut Criterion, n: i64) {
+    let mut group = c.benchmark_group(format!("Build from a vec of {n} i64 items"));
+    group.sample_size(10);
+
+    let mut rng = SmallRng::seed_from_u64(SEED);
+    let mut items: Vec<i64> = (0..n).collect();
+    items.shuffle(&mut rng);
+
+    group.bench_function("Beap::from", |b| {
+        b.iter(|| black_box(Beap::from(items.clone())))
+    });
+
+    #[cfg(feature = "rayon")]
+    group.bench_function("Beap::par_from_vec", |b| {
+        b.iter(|| black_box(Beap::par_from_vec(items.clone())))
+    });
+
+    group.finish();
+}
+
+fn bench_append(c: &mut Criterion) {
+    call_append_group(c, 1000);
+    call_append_group(c, 10000);
+    call_append_group(c, 100000);
+}
+
+fn call_append_group(c: &mut Criterion, n: i64) {
+    let mut group = c.benchmark_group(format!("Append 2x{n} i64 items"));
+    group.sample_size(30);
+
+    let mut rng = SmallRng::seed_from_u64(SEED);
+    let mut items: Vec<i64> = (0..2 * n).collect();
+    items.shuffle(&mut rng);
+    let left = Beap::from(items[..n as usize].to_vec());
+    let right = Beap::from(items[n as usize..].to_vec());
+
+    group.bench_function("Beap::append", |b| {
+        b.iter(|| {
+            let mut beap = left.clone();
+            let mut other = right.clone();
+            beap.append(&mut other);
+            black_box((beap, other))
+        })
+    });
+
+    group.bench_function("Concatenate & rebuild", |b| {
+        b.iter(|| {
+            let mut data = left.clone().into_vec();
+            data.append(&mut right.clone().into_vec());
+            black_box(Beap::from(data))
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_push_iter(c: &mut Criterion) {
+    call_push_iter_group(c, 1000);
+    call_push_iter_group(c, 10000);
+}
+
+fn call_push_iter_group(c: &mut Criterion, n: i64) {
+    let mut group = c.benchmark_group(format!("Bulk push {n} i64 items onto {n}"));
+    group.sample_size(30);
+
+    let mut rng = SmallRng::seed_from_u64(SEED);
+    let mut items: Vec<i64> = (0..2 * n).collect();
+    items.shuffle(&mut rng);
+    let beap = Beap::from(items[..n as usize].to_vec());
+    let items = &items[n as usize..];
+
+    group.bench_function("Beap::extend", |b| {
+        b.iter(|| {
+            let mut beap = beap.clone();
+            beap.extend(items.iter().copied());
+            black_box(beap)
+        })
+    });
+
+    group.bench_function("Beap::push_iter", |b| {
+        b.iter(|| {
+            let mut beap = beap.clone();
+            beap.push_iter(items.iter().copied());
+            black_box(beap)
+        })
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     basics,
     bench_push,
     bench_pop,
+    bench_push_pop,
     bench_push_peek,
     bench_contains,
-    bench_push_tail
+    bench_push_tail,
+    bench_from_vec,
+    bench_append,
+    bench_push_iter
 );
 criterion_main!(basics);
@@ -0,0 +1,21 @@
+//! Fuzzing support with `arbitrary`.
+use super::Beap;
+use alloc::vec::Vec;
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+impl<'a, T: Arbitrary<'a> + Ord> Arbitrary<'a> for Beap<T> {
+    /// Generates an arbitrary vector and converts it into a beap,
+    /// so the result always satisfies the beap property.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Vec::<T>::arbitrary(u).map(Beap::from)
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        Vec::<T>::arbitrary_take_rest(u).map(Beap::from)
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Vec::<T>::size_hint(depth)
+    }
+}
@@ -0,0 +1,390 @@
+//! Beap wrapper caching the position of the smallest item.
+use super::Beap;
+use alloc::vec::Vec;
+
+/// A priority queue implemented with a bi-parental heap,
+/// which keeps track of its smallest item.
+///
+/// [`Beap::tail`] scans the last block on every call, taking *O*(sqrt(*2n*)).
+/// `CachedTailBeap<T>` remembers the position of the smallest item instead,
+/// so [`tail`](CachedTailBeap::tail) is *O*(1):
+///
+/// - `push` and `pop` follow the cached item through the swaps they make,
+///   and `push` replaces it when the new item is smaller;
+/// - removing the cached item itself (`pop_tail`, `remove`, or `pop` of the last item)
+///   invalidates the cache, and the new smallest item is found right away
+///   in *O*(sqrt(*2n*)), the same as the removal costs.
+///
+/// This will be a max-heap.
+///
+/// # Examples
+///
+/// ```
+/// use beap::CachedTailBeap;
+///
+/// let mut beap = CachedTailBeap::new();
+/// beap.push(5);
+/// beap.push(1);
+/// beap.push(3);
+///
+/// assert_eq!(beap.tail(), Some(&1));
+/// assert_eq!(beap.pop_tail(), Some(1));
+/// assert_eq!(beap.tail(), Some(&3));
+/// assert_eq!(beap.pop(), Some(5));
+/// assert_eq!(beap.tail(), Some(&3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachedTailBeap<T> {
+    beap: Beap<T>,
+    tail: Option<usize>,
+}
+
+impl<T> Default for CachedTailBeap<T> {
+    fn default() -> Self {
+        CachedTailBeap::new()
+    }
+}
+
+impl<T> CachedTailBeap<T> {
+    /// Creates an empty `CachedTailBeap`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::CachedTailBeap;
+    /// let mut beap = CachedTailBeap::new();
+    /// beap.push(4);
+    /// assert_eq!(beap.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn new() -> CachedTailBeap<T> {
+        CachedTailBeap {
+            beap: Beap::new(),
+            tail: None,
+        }
+    }
+
+    /// Returns the greatest item in the beap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::CachedTailBeap;
+    /// let mut beap = CachedTailBeap::new();
+    /// assert_eq!(beap.peek(), None);
+    ///
+    /// beap.extend([1, 5, 2]);
+    /// assert_eq!(beap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(1).
+    pub fn peek(&self) -> Option<&T> {
+        self.beap.peek()
+    }
+
+    /// Returns the smallest item in the beap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::CachedTailBeap;
+    /// let mut beap = CachedTailBeap::new();
+    /// assert_eq!(beap.tail(), None);
+    ///
+    /// beap.extend([4, 1, 3]);
+    /// assert_eq!(beap.tail(), Some(&1));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(1).
+    pub fn tail(&self) -> Option<&T> {
+        self.tail.map(|pos| &self.beap.data[pos])
+    }
+
+    /// Returns the length of the beap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::CachedTailBeap;
+    /// let beap = CachedTailBeap::from(vec![1, 3]);
+    ///
+    /// assert_eq!(beap.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.beap.len()
+    }
+
+    /// Checks if the beap is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::CachedTailBeap;
+    /// let mut beap = CachedTailBeap::new();
+    /// assert!(beap.is_empty());
+    ///
+    /// beap.push(3);
+    /// assert!(!beap.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.beap.is_empty()
+    }
+
+    /// Drops all items from the beap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::CachedTailBeap;
+    /// let mut beap = CachedTailBeap::from(vec![1, 3]);
+    /// assert!(!beap.is_empty());
+    ///
+    /// beap.clear();
+    /// assert!(beap.is_empty());
+    /// assert_eq!(beap.tail(), None);
+    /// ```
+    pub fn clear(&mut self) {
+        self.beap.clear();
+        self.tail = None;
+    }
+
+    /// Consumes the `CachedTailBeap` and returns the underlying `Beap<T>`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::CachedTailBeap;
+    /// let beap = CachedTailBeap::from(vec![1, 3, 2]);
+    ///
+    /// let inner = beap.into_inner();
+    /// assert_eq!(inner.into_sorted_vec(), vec![1, 2, 3]);
+    /// ```
+    pub fn into_inner(self) -> Beap<T> {
+        self.beap
+    }
+}
+
+impl<T: Ord> CachedTailBeap<T> {
+    /// Pushes an item onto the beap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::CachedTailBeap;
+    /// let mut beap = CachedTailBeap::new();
+    /// beap.push(3);
+    /// beap.push(5);
+    /// beap.push(1);
+    ///
+    /// assert_eq!(beap.len(), 3);
+    /// assert_eq!(beap.peek(), Some(&5));
+    /// assert_eq!(beap.tail(), Some(&1));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn push(&mut self, item: T) {
+        let pos = self.len();
+        let smaller = self.tail().is_none_or(|tail| item < *tail);
+        if smaller {
+            self.tail = Some(pos);
+        }
+
+        let tail = &mut self.tail;
+        self.beap.push_with(item, &mut |a, b| follow(tail, a, b));
+    }
+
+    /// Removes the greatest item from the beap and returns it, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::CachedTailBeap;
+    /// let mut beap = CachedTailBeap::from(vec![1, 3]);
+    ///
+    /// assert_eq!(beap.pop(), Some(3));
+    /// assert_eq!(beap.pop(), Some(1));
+    /// assert_eq!(beap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            self.remove_index(0)
+        }
+    }
+
+    /// Removes the smallest item from the beap and returns it, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::CachedTailBeap;
+    /// let mut beap = CachedTailBeap::from(vec![1, 3, 2]);
+    ///
+    /// assert_eq!(beap.pop_tail(), Some(1));
+    /// assert_eq!(beap.tail(), Some(&2));
+    /// assert_eq!(beap.pop_tail(), Some(2));
+    /// assert_eq!(beap.pop_tail(), Some(3));
+    /// assert_eq!(beap.pop_tail(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn pop_tail(&mut self) -> Option<T> {
+        self.remove_index(self.tail?)
+    }
+
+    /// Returns true if the beap contains a value.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::CachedTailBeap;
+    /// let beap = CachedTailBeap::from(vec![1, 5, 3]);
+    ///
+    /// assert!(beap.contains(&3));
+    /// assert!(!beap.contains(&4));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn contains(&self, val: &T) -> bool {
+        self.beap.contains(val)
+    }
+
+    /// Removes a value from the beap. Returns whether the value was present in the beap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::CachedTailBeap;
+    /// let mut beap = CachedTailBeap::from(vec![1, 5, 3]);
+    ///
+    /// assert!(beap.remove(&1));
+    /// assert!(!beap.remove(&4));
+    /// assert_eq!(beap.tail(), Some(&3));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn remove(&mut self, val: &T) -> bool {
+        match self.beap.index(val) {
+            Some(pos) => {
+                self.remove_index(pos);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Consumes the `CachedTailBeap` and returns a vector in sorted (ascending) order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::CachedTailBeap;
+    /// let beap = CachedTailBeap::from(vec![3, 1, 2]);
+    ///
+    /// assert_eq!(beap.into_sorted_vec(), vec![1, 2, 3]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)).
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.beap.into_sorted_vec()
+    }
+
+    fn remove_index(&mut self, pos: usize) -> Option<T> {
+        if self.tail == Some(pos) {
+            self.tail = None;
+        }
+
+        let tail = &mut self.tail;
+        let item = self
+            .beap
+            .remove_index_with(pos, &mut |a, b| follow(tail, a, b));
+
+        if self.tail.is_none() {
+            self.tail = self.beap.tail_with_index().map(|(pos, _)| pos);
+        }
+        item
+    }
+}
+
+/// Updates the cached position after swapping the items at `a` and `b`.
+fn follow(tail: &mut Option<usize>, a: usize, b: usize) {
+    if *tail == Some(a) {
+        *tail = Some(b);
+    } else if *tail == Some(b) {
+        *tail = Some(a);
+    }
+}
+
+impl<T: Ord> From<Beap<T>> for CachedTailBeap<T> {
+    fn from(beap: Beap<T>) -> CachedTailBeap<T> {
+        CachedTailBeap {
+            tail: beap.tail_with_index().map(|(pos, _)| pos),
+            beap,
+        }
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for CachedTailBeap<T> {
+    fn from(vec: Vec<T>) -> CachedTailBeap<T> {
+        CachedTailBeap::from(Beap::from(vec))
+    }
+}
+
+impl<T: Ord> FromIterator<T> for CachedTailBeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> CachedTailBeap<T> {
+        CachedTailBeap::from(iter.into_iter().collect::<Beap<T>>())
+    }
+}
+
+impl<T: Ord> Extend<T> for CachedTailBeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
@@ -1,7 +1,11 @@
 //! Beap logic.
-use crate::PosMut;
+use crate::{ManyPosMut, PosMut};
 
-use super::{Beap, PeekMut, TailMut};
+use super::{Beap, PeekMut, Summary, TailMut};
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::ops::RangeBounds;
+use core::ptr;
 
 impl<T: Ord> Beap<T> {
     /// Pushes an item onto the beap.
@@ -25,6 +29,14 @@ impl<T: Ord> Beap<T> {
     ///
     /// *O*(sqrt(*2n*))
     pub fn push(&mut self, item: T) {
+        self.push_with(item, &mut |_, _| {});
+    }
+
+    /// Same as `push`, but reports every swap of two positions to `on_swap`.
+    pub(crate) fn push_with<F>(&mut self, item: T, on_swap: &mut F)
+    where
+        F: FnMut(usize, usize),
+    {
         if let Some((_, end)) = self.span(self.height) {
             if self.data.len() > end {
                 self.height += 1;
@@ -34,7 +46,105 @@ impl<T: Ord> Beap<T> {
         }
 
         self.data.push(item);
-        self.siftup(self.data.len() - 1, self.height);
+        self.siftup_with(self.data.len() - 1, self.height, on_swap);
+    }
+
+    /// Pushes an item onto the beap if there is spare capacity,
+    /// otherwise returns the item back without reallocating.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::with_capacity(2);
+    /// let capacity = beap.capacity();
+    ///
+    /// for x in 0..capacity {
+    ///     assert_eq!(beap.push_within_capacity(x), Ok(()));
+    /// }
+    /// assert_eq!(beap.push_within_capacity(100), Err(100));
+    /// assert_eq!(beap.capacity(), capacity);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn push_within_capacity(&mut self, item: T) -> Result<(), T> {
+        if self.len() < self.capacity() {
+            self.push(item);
+            Ok(())
+        } else {
+            Err(item)
+        }
+    }
+
+    /// Pushes all the items of an iterator onto the beap, restoring the beap property once.
+    ///
+    /// Unlike [`extend`](Extend::extend), which pushes the items one by one,
+    /// this appends them to the underlying vector and then rebuilds the beap,
+    /// which is faster when many items are added at once.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([3, 1]);
+    ///
+    /// beap.push_iter(vec![7, 0, 4]);
+    /// assert_eq!(beap.peek(), Some(&7));
+    /// assert_eq!(beap.into_sorted_vec(), vec![0, 1, 3, 4, 7]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*((*n* + *m*)log(*n* + *m*)), where *m* is the number of pushed items,
+    /// compared to *O*(*m* sqrt(*2n*)) for pushing them one by one.
+    pub fn push_iter<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let len = self.data.len();
+        self.data.extend(iter);
+        if self.data.len() > len {
+            self.rebuild();
+        }
+    }
+
+    /// Pushes an item onto the beap, keeping at most `cap` greatest items.
+    ///
+    /// If the beap has fewer than `cap` items, `item` is pushed and `None` is returned.
+    /// Otherwise, if `item` is greater than the smallest item, the smallest item is
+    /// replaced with it and returned; if not, `item` itself is returned
+    /// and the beap is left unchanged. The beap is never shrunk to `cap`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::new();
+    ///
+    /// assert_eq!(beap.push_bounded(3, 2), None);
+    /// assert_eq!(beap.push_bounded(5, 2), None);
+    /// assert_eq!(beap.push_bounded(1, 2), Some(1));
+    /// assert_eq!(beap.push_bounded(4, 2), Some(3));
+    /// assert_eq!(beap.into_sorted_vec(), vec![4, 5]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn push_bounded(&mut self, item: T, cap: usize) -> Option<T> {
+        if self.len() < cap {
+            self.push(item);
+            return None;
+        }
+        match self.tail_index() {
+            Some(idx) if self.data[idx] < item => self.replace_index(idx, item),
+            _ => Some(item),
+        }
     }
 
     /// Removes the greatest item from the beap and returns it, or `None` if it is empty.
@@ -62,7 +172,7 @@ impl<T: Ord> Beap<T> {
                     if start == self.data.len() {
                         self.height -= 1;
                     }
-                    std::mem::swap(&mut item, &mut self.data[0]);
+                    core::mem::swap(&mut item, &mut self.data[0]);
                     self.siftdown(0, 1);
                 }
             } else {
@@ -72,6 +182,62 @@ impl<T: Ord> Beap<T> {
         })
     }
 
+    /// Removes the greatest item from the beap and returns it if the predicate returns `true`,
+    /// or `None` if the predicate returns `false` or the beap is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from(vec![1, 3, 7]);
+    /// let pred = |x: &i32| *x > 2;
+    ///
+    /// assert_eq!(beap.pop_if(pred), Some(7));
+    /// assert_eq!(beap.pop_if(pred), Some(3));
+    /// assert_eq!(beap.pop_if(pred), None);
+    /// assert_eq!(beap.peek(), Some(&1));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(1) if the predicate returns `false`, otherwise *O*(sqrt(*2n*)).
+    pub fn pop_if<F: FnOnce(&T) -> bool>(&mut self, f: F) -> Option<T> {
+        if f(self.peek()?) {
+            self.pop()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the greatest item in the beap,
+    /// or pushes the result of `f` and returns it if the beap is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::new();
+    ///
+    /// assert_eq!(beap.peek_or_insert_with(|| 3), &3);
+    /// beap.push(5);
+    /// assert_eq!(beap.peek_or_insert_with(|| 10), &5);
+    /// assert_eq!(beap.len(), 2);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(1).
+    pub fn peek_or_insert_with<F: FnOnce() -> T>(&mut self, f: F) -> &T {
+        if self.is_empty() {
+            self.push(f());
+        }
+        &self.data[0]
+    }
+
     /// Effective equivalent to a sequential `push()` and `pop()` calls.
     ///
     /// # Examples
@@ -100,14 +266,57 @@ impl<T: Ord> Beap<T> {
     /// And unlike the sequential call of `push()` and `pop()`, the resizing never happens.
     pub fn pushpop(&mut self, mut item: T) -> T {
         if !self.is_empty() && self.data[0] > item {
-            std::mem::swap(&mut item, &mut self.data[0]);
+            core::mem::swap(&mut item, &mut self.data[0]);
             self.siftdown(0, 1);
         }
         item
     }
 
+    /// Replaces the greatest item with `item` and returns the replaced one,
+    /// or pushes `item` and returns `None` if the beap is empty.
+    ///
+    /// Effective equivalent to a sequential `pop()` and `push()` calls.
+    /// Unlike [`pushpop`](Beap::pushpop), the new item always ends up in the beap,
+    /// even if it is greater than the current maximum.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::new();
+    /// assert_eq!(beap.replace_root(5), None);
+    /// assert_eq!(beap.peek(), Some(&5));
+    ///
+    /// beap.push(3);
+    /// assert_eq!(beap.replace_root(1), Some(5));
+    /// assert_eq!(beap.peek(), Some(&3));
+    ///
+    /// assert_eq!(beap.replace_root(10), Some(3));
+    /// assert_eq!(beap.peek(), Some(&10));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)). Unlike the sequential call of `pop()` and `push()`,
+    /// the resizing never happens.
+    pub fn replace_root(&mut self, mut item: T) -> Option<T> {
+        if self.is_empty() {
+            self.push(item);
+            None
+        } else {
+            core::mem::swap(&mut item, &mut self.data[0]);
+            self.siftdown(0, 1);
+            Some(item)
+        }
+    }
+
     /// Returns true if the beap contains a value.
     ///
+    /// The value may be any borrowed form of the beap's element type,
+    /// but the ordering on the borrowed form *must* match the ordering on the element type.
+    ///
     /// # Examples
     ///
     /// Basic usage:
@@ -124,12 +333,113 @@ impl<T: Ord> Beap<T> {
     /// # Time complexity
     ///
     /// *O*(sqrt(*2n*))
-    pub fn contains(&self, val: &T) -> bool {
+    pub fn contains<Q>(&self, val: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         self.index(val).is_some()
     }
 
+    /// Returns the number of elements in the beap equal to a value.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([1, 5, 3, 5]);
+    ///
+    /// assert_eq!(beap.count(&5), 2);
+    /// assert_eq!(beap.count(&1), 1);
+    /// assert_eq!(beap.count(&0), 0);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*), unlike [`contains`](Beap::contains) which needs only *O*(sqrt(*2n*)).
+    pub fn count(&self, val: &T) -> usize {
+        self.data.iter().filter(|&x| x == val).count()
+    }
+
+    /// Returns the number of elements in the beap greater than or equal to a value.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([1, 5, 3, 5]);
+    ///
+    /// assert_eq!(beap.rank(&5), 2);
+    /// assert_eq!(beap.rank(&2), 3);
+    /// assert_eq!(beap.rank(&6), 0);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*). The beap layout would allow to count the elements in *O*(sqrt(*2n*))
+    /// by walking the boundary of the greater elements the same way [`index`](Beap::index)
+    /// searches for a value, but this is not implemented yet.
+    pub fn rank(&self, val: &T) -> usize {
+        self.data.iter().filter(|&x| x >= val).count()
+    }
+
+    /// Returns the number of elements in the beap within the inclusive range `[lo, hi]`.
+    ///
+    /// Returns 0 if `lo > hi`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([1, 5, 3, 5, 8]);
+    ///
+    /// assert_eq!(beap.count_in_range(&3, &5), 3);
+    /// assert_eq!(beap.count_in_range(&6, &7), 0);
+    /// assert_eq!(beap.count_in_range(&5, &3), 0);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*).
+    pub fn count_in_range(&self, lo: &T, hi: &T) -> usize {
+        self.data.iter().filter(|&x| lo <= x && x <= hi).count()
+    }
+
+    /// Returns `true` if any element of the beap is within the inclusive range `[lo, hi]`.
+    ///
+    /// Returns `false` if `lo > hi`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([1, 5, 3, 5, 8]);
+    ///
+    /// assert!(beap.any_in_range(&6, &8));
+    /// assert!(!beap.any_in_range(&6, &7));
+    /// assert!(!beap.any_in_range(&8, &6));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*) in the worst case, the scan stops at the first element found.
+    pub fn any_in_range(&self, lo: &T, hi: &T) -> bool {
+        self.data.iter().any(|x| lo <= x && x <= hi)
+    }
+
     /// Removes a value from the beap. Returns whether the value was present in the beap.
     ///
+    /// The value may be any borrowed form of the beap's element type,
+    /// but the ordering on the borrowed form *must* match the ordering on the element type.
+    ///
     /// # Examples
     ///
     /// Basic usage:
@@ -146,7 +456,11 @@ impl<T: Ord> Beap<T> {
     /// # Time complexity
     ///
     /// *O*(sqrt(*2n*))
-    pub fn remove(&mut self, val: &T) -> bool {
+    pub fn remove<Q>(&mut self, val: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
         match self.index(val) {
             Some(idx) => {
                 self.remove_index(idx);
@@ -156,6 +470,216 @@ impl<T: Ord> Beap<T> {
         }
     }
 
+    /// Removes every occurrence of a value from the beap.
+    /// Returns the number of removed elements.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 3, 5, 3, 3]);
+    ///
+    /// assert_eq!(beap.remove_all(&3), 3);
+    /// assert_eq!(beap.remove_all(&3), 0);
+    /// assert_eq!(beap.into_sorted_vec(), vec![1, 5]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*k* sqrt(*2n*)), where *k* is the number of removed elements.
+    pub fn remove_all(&mut self, val: &T) -> usize {
+        let mut count = 0;
+        while let Some(idx) = self.index(val) {
+            self.remove_index(idx);
+            count += 1;
+        }
+        count
+    }
+
+    /// Calls `f` on every value in the underlying vector, in arbitrary order,
+    /// allowing it to modify each value.
+    ///
+    /// The beap is rebuilt afterwards to restore the beap property,
+    /// even if `f` panics.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 2, 3, 4]);
+    ///
+    /// beap.for_each_mut(|x| *x = -*x);
+    ///
+    /// assert_eq!(beap.peek(), Some(&-1));
+    /// assert_eq!(beap.into_sorted_vec(), vec![-4, -3, -2, -1]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*) calls of `f` plus *O*(*n*log(*n*)) to rebuild the beap
+    /// regardless of how many values were modified.
+    pub fn for_each_mut<F: FnMut(&mut T)>(&mut self, f: F) {
+        /// Rebuilds the beap when dropped, including on unwinding.
+        struct Rebuild<'a, T: Ord>(&'a mut Beap<T>);
+
+        impl<T: Ord> Drop for Rebuild<'_, T> {
+            fn drop(&mut self) {
+                self.0.rebuild();
+            }
+        }
+
+        let guard = Rebuild(self);
+        guard.0.data.iter_mut().for_each(f);
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all elements `e` for which `f(&e)` returns `false`.
+    /// The elements are filtered in place, so the capacity of the beap is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([-10, -5, 1, 2, 4, 13]);
+    ///
+    /// beap.retain(|x| x % 2 == 0);
+    /// assert_eq!(beap.len(), 3);
+    /// assert_eq!(beap.into_sorted_vec(), [-10, 2, 4]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*) for the filtering plus *O*(*n*log(*n*)) to rebuild the beap
+    /// if any element has been removed.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        let len = self.len();
+        self.data.retain(f);
+        if self.len() < len {
+            self.rebuild();
+        }
+    }
+
+    /// Retains only the elements specified by the predicate,
+    /// and returns the removed elements in arbitrary order.
+    ///
+    /// Unlike [`extract_if`](Beap::extract_if), all the elements are
+    /// partitioned eagerly.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([-10, -5, 1, 2, 4, 13]);
+    ///
+    /// let mut odd = beap.extract_retain(|x| x % 2 == 0);
+    /// odd.sort();
+    /// assert_eq!(odd, [-5, 1, 13]);
+    /// assert_eq!(beap.into_sorted_vec(), [-10, 2, 4]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*) for the filtering plus *O*(*n*log(*n*)) to rebuild the beap
+    /// if any element has been removed.
+    pub fn extract_retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> Vec<T> {
+        let removed: Vec<T> = self.data.extract_if(.., |x| !f(x)).collect();
+        if !removed.is_empty() {
+            self.rebuild();
+        }
+        removed
+    }
+
+    /// Removes duplicate values, keeping one item of each distinct value.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 3, 2, 3, 1, 3]);
+    ///
+    /// beap.dedup();
+    /// assert_eq!(beap.into_sorted_vec(), vec![1, 2, 3]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)).
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Removes all but the first of consecutive items that resolve to the same key,
+    /// where the items are visited in descending order.
+    ///
+    /// If the key is monotonic with respect to the order of the items,
+    /// all the items with equal keys are consecutive.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([10, 20, 21, 30, 29]);
+    ///
+    /// beap.dedup_by_key(|x| *x / 10);
+    /// assert_eq!(beap.into_sorted_vec(), vec![10, 29, 30]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)).
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Removes all but the first of consecutive items satisfying a given equality relation,
+    /// where the items are visited in descending order.
+    ///
+    /// The `same_bucket` function is passed references to two items from the beap,
+    /// the latter of which is greater or equal, and must determine if they compare equal.
+    /// If it returns `true`, the former item is removed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 2, 4, 5, 7]);
+    ///
+    /// beap.dedup_by(|a, b| *b - *a <= 1);
+    /// assert_eq!(beap.into_sorted_vec(), vec![2, 5, 7]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)).
+    pub fn dedup_by<F>(&mut self, same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        self.data.sort_unstable_by(|x, y| y.cmp(x));
+        self.data.dedup_by(same_bucket);
+        // The closure may have changed the items, so the order is restored again.
+        self.rebuild();
+    }
+
     /// Replaces the first found element with the value ```old``` with the
     /// value ```new```, returns ```true``` if the element ```old``` was found.
     ///
@@ -172,25 +696,302 @@ impl<T: Ord> Beap<T> {
     /// assert!(beap.replace(&10, 100));
     /// assert!(!beap.replace(&1, 200));
     ///
-    /// assert_eq!(beap.into_sorted_vec(), vec![5, 100]);
+    /// assert_eq!(beap.into_sorted_vec(), vec![5, 100]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn replace(&mut self, old: &T, new: T) -> bool {
+        let idx = self.index(old);
+        match idx {
+            Some(pos) => {
+                self.data[pos] = new;
+                self.repair(pos);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replaces the element at the given position with `new` and returns the old value,
+    /// or `None` if the position is out of bounds.
+    ///
+    /// Unlike [`replace`](Beap::replace), no search for the element is needed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 2, 3]);
+    ///
+    /// assert_eq!(beap.replace_index(2, 10), Some(1));
+    /// assert_eq!(beap.replace_index(3, 0), None);
+    /// assert_eq!(beap.peek(), Some(&10));
+    /// assert_eq!(beap.into_sorted_vec(), vec![2, 3, 10]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn replace_index(&mut self, pos: usize, new: T) -> Option<T> {
+        let old = core::mem::replace(self.data.get_mut(pos)?, new);
+        self.repair(pos);
+        Some(old)
+    }
+
+    /// Applies `f` to the element at the given position and restores the beap property.
+    ///
+    /// Returns `false` if the position is out of bounds. Unlike [`get_mut`](Beap::get_mut),
+    /// no guard is held while user code runs.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 2, 3]);
+    ///
+    /// assert!(beap.modify_index(2, |x| *x += 10));
+    /// assert!(!beap.modify_index(3, |x| *x += 10));
+    /// assert_eq!(beap.peek(), Some(&11));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn modify_index<F: FnOnce(&mut T)>(&mut self, pos: usize, f: F) -> bool {
+        match self.data.get_mut(pos) {
+            Some(item) => {
+                f(item);
+                self.repair(pos);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replaces the element at the given position with a greater or equal value.
+    ///
+    /// Returns `false` if the position is out of bounds. This is cheaper than
+    /// [`replace_index`](Beap::replace_index), since the element can only move up.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` is less than the current value.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 2, 3]);
+    ///
+    /// assert!(beap.increase_key(2, 10));
+    /// assert!(!beap.increase_key(3, 10));
+    /// assert_eq!(beap.peek(), Some(&10));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn increase_key(&mut self, pos: usize, new: T) -> bool {
+        match self.data.get_mut(pos) {
+            Some(item) => {
+                debug_assert!(new >= *item, "the new key is less than the current one");
+                *item = new;
+                self.siftup(pos, block_of(pos));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replaces the element at the given position with a less or equal value.
+    ///
+    /// Returns `false` if the position is out of bounds. This is cheaper than
+    /// [`replace_index`](Beap::replace_index), since the element can only move down.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `new` is greater than the current value.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 2, 3]);
+    ///
+    /// assert!(beap.decrease_key(0, 0));
+    /// assert!(!beap.decrease_key(3, 0));
+    /// assert_eq!(beap.peek(), Some(&2));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn decrease_key(&mut self, pos: usize, new: T) -> bool {
+        match self.data.get_mut(pos) {
+            Some(item) => {
+                debug_assert!(new <= *item, "the new key is greater than the current one");
+                *item = new;
+                self.siftdown(pos, block_of(pos));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Swaps the elements at two positions and restores the beap property.
+    ///
+    /// Returns `false` if either position is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 2, 3, 4]);
+    ///
+    /// assert!(beap.swap_indices(0, 3));
+    /// assert!(!beap.swap_indices(0, 4));
+    /// assert_eq!(beap.peek(), Some(&4));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn swap_indices(&mut self, i: usize, j: usize) -> bool {
+        if i >= self.len() || j >= self.len() {
+            return false;
+        }
+        if i != j {
+            self.data.swap(i, j);
+            self.repair_many(&[i, j]);
+        }
+        true
+    }
+
+    /// Returns the smallest item in the beap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::new();
+    /// assert_eq!(beap.tail(), None);
+    ///
+    /// beap.push(9);
+    /// beap.push(3);
+    /// beap.push(6);
+    /// assert_eq!(beap.tail(), Some(&3));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*))
+    pub fn tail(&self) -> Option<&T> {
+        self.tail_index().map(|i| &self.data[i])
+    }
+
+    /// Returns the smallest and the greatest items in the beap,
+    /// or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::new();
+    /// assert_eq!(beap.min_and_max(), None);
+    ///
+    /// beap.push(9);
+    /// beap.push(3);
+    /// beap.push(6);
+    /// assert_eq!(beap.min_and_max(), Some((&3, &9)));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)), the same as a single call to [`tail`](Beap::tail).
+    pub fn min_and_max(&self) -> Option<(&T, &T)> {
+        self.tail_index().map(|i| (&self.data[i], &self.data[0]))
+    }
+
+    /// Returns the greatest item, the smallest item and the length of the beap
+    /// in a single [`Summary`], or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::new();
+    /// assert_eq!(beap.summary(), None);
+    ///
+    /// beap.extend([9, 3, 6]);
+    /// let summary = beap.summary().unwrap();
+    /// assert_eq!(summary.max, &9);
+    /// assert_eq!(summary.min, &3);
+    /// assert_eq!(summary.len, 3);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)), the same as a single call to [`tail`](Beap::tail).
+    pub fn summary(&self) -> Option<Summary<'_, T>> {
+        self.min_and_max().map(|(min, max)| Summary {
+            max,
+            min,
+            len: self.len(),
+        })
+    }
+
+    /// Returns the smallest item in the beap together with its index,
+    /// or `None` if it is empty.
+    ///
+    /// The index can be passed to [`remove_index`](Beap::remove_index)
+    /// or [`get_mut`](Beap::get_mut).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::new();
+    /// assert_eq!(beap.tail_with_index(), None);
+    ///
+    /// beap.push(9);
+    /// beap.push(3);
+    /// beap.push(6);
+    /// let (idx, &min) = beap.tail_with_index().unwrap();
+    /// assert_eq!(min, 3);
+    /// assert_eq!(beap.remove_index(idx), Some(3));
     /// ```
     ///
     /// # Time complexity
     ///
     /// *O*(sqrt(*2n*)).
-    pub fn replace(&mut self, old: &T, new: T) -> bool {
-        let idx = self.index(old);
-        match idx {
-            Some(pos) => {
-                self.data[pos] = new;
-                self.repair(pos);
-                true
-            }
-            None => false,
-        }
+    pub fn tail_with_index(&self) -> Option<(usize, &T)> {
+        self.tail_index().map(|i| (i, &self.data[i]))
     }
 
-    /// Returns the smallest item in the beap, or `None` if it is empty.
+    /// Returns the index of the smallest item in the beap, or `None` if it is empty.
+    ///
+    /// The index can be passed to [`remove_index`](Beap::remove_index)
+    /// or [`get_mut`](Beap::get_mut).
     ///
     /// # Examples
     ///
@@ -198,30 +999,31 @@ impl<T: Ord> Beap<T> {
     ///
     /// ```
     /// use beap::Beap;
-    /// let mut beap = Beap::new();
-    /// assert_eq!(beap.tail(), None);
+    /// let mut beap = Beap::from([9, 3, 6]);
     ///
-    /// beap.push(9);
-    /// beap.push(3);
-    /// beap.push(6);
-    /// assert_eq!(beap.tail(), Some(&3));
+    /// let pos = beap.position_of_min().unwrap();
+    /// assert_eq!(beap.get(pos), Some(&3));
+    /// *beap.get_mut(pos).unwrap() = 10;
+    /// assert_eq!(beap.peek(), Some(&10));
     /// ```
     ///
     /// # Time complexity
     ///
-    /// *O*(sqrt(*2n*))
-    pub fn tail(&self) -> Option<&T> {
-        self.span(self.height).and_then(|(start, end)| {
-            if self.height == 1 {
-                self.data.first()
-            } else {
-                let empty = end + 1 - self.len();
-                self.data.get(
-                    ((start - empty)..=(end - empty))
-                        .min_by_key(|&i| &self.data[i])
-                        .unwrap(),
-                )
-            }
+    /// *O*(sqrt(*2n*)).
+    pub fn position_of_min(&self) -> Option<usize> {
+        self.tail_index()
+    }
+
+    /// Index of the smallest item, found among the last `height` positions.
+    ///
+    /// If the last block is not full, these also cover the end of the previous block,
+    /// whose items have no children and so may hold the minimum too.
+    fn tail_index(&self) -> Option<usize> {
+        self.span(self.height).map(|(start, end)| {
+            let empty = end + 1 - self.len();
+            ((start - empty)..=(end - empty))
+                .min_by_key(|&i| &self.data[i])
+                .unwrap()
         })
     }
 
@@ -294,19 +1096,11 @@ impl<T: Ord> Beap<T> {
     ///
     /// *O*(sqrt(*2n*))
     pub fn tail_mut(&mut self) -> Option<TailMut<'_, T>> {
-        if let Some((start, end)) = self.span(self.height) {
-            let empty = end + 1 - self.len();
-            let idx = ((start - empty)..=(end - empty))
-                .min_by_key(|&i| &self.data[i])
-                .unwrap();
-            Some(TailMut {
-                beap: self,
-                sift: false,
-                pos: idx,
-            })
-        } else {
-            None
-        }
+        self.tail_index().map(|pos| TailMut {
+            beap: self,
+            sift: false,
+            pos,
+        })
     }
 
     /// Returns a mutable reference to the item with given position, or
@@ -353,6 +1147,47 @@ impl<T: Ord> Beap<T> {
         }
     }
 
+    /// Returns a structure wrapping mutable references to the items at the given
+    /// positions, or `None` if any position is out of bounds or occurs twice.
+    ///
+    /// Note: If the `ManyPosMut` value is leaked, the beap may be in an
+    /// inconsistent state.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 2, 3, 4, 5]);
+    /// assert!(beap.get_many_mut([0, 5]).is_none());
+    /// assert!(beap.get_many_mut([1, 1]).is_none());
+    /// {
+    ///     let mut vals = beap.get_many_mut([0, 4]).unwrap();
+    ///     let [a, b] = vals.values_mut();
+    ///     assert_eq!((*a, *b), (5, 1));
+    ///     *a = 0;
+    ///     *b = 10;
+    /// }
+    /// assert_eq!(beap.peek(), Some(&10));
+    /// assert_eq!(beap.tail(), Some(&0));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*N* sqrt(*2n*)) to restore the heap property if the items have been changed.
+    pub fn get_many_mut<const N: usize>(
+        &mut self,
+        positions: [usize; N],
+    ) -> Option<ManyPosMut<'_, T, N>> {
+        self.data.get_disjoint_mut(positions).ok()?;
+        Some(ManyPosMut {
+            beap: self,
+            sift: false,
+            positions,
+        })
+    }
+
     /// Removes the smallest item from the beap and returns it, or `None` if it is empty.
     ///
     /// # Examples
@@ -372,14 +1207,37 @@ impl<T: Ord> Beap<T> {
     ///
     /// *O*(sqrt(*2n*)).
     pub fn pop_tail(&mut self) -> Option<T> {
-        self.span(self.height)
-            .and_then(|(start, end)| {
-                let empty = end + 1 - self.len();
-                ((start - empty)..=(end - empty))
-                    .min_by_key(|&i| &self.data[i])
-                    .map(|idx| self.remove_index(idx))
-            })
-            .flatten()
+        self.tail_index().and_then(|idx| self.remove_index(idx))
+    }
+
+    /// Removes the smallest item from the beap and returns it if the predicate returns `true`,
+    /// or `None` if the predicate returns `false` or the beap is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from(vec![1, 3, 7]);
+    /// let pred = |x: &i32| *x < 5;
+    ///
+    /// assert_eq!(beap.pop_tail_if(pred), Some(1));
+    /// assert_eq!(beap.pop_tail_if(pred), Some(3));
+    /// assert_eq!(beap.pop_tail_if(pred), None);
+    /// assert_eq!(beap.peek(), Some(&7));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn pop_tail_if<F: FnOnce(&T) -> bool>(&mut self, f: F) -> Option<T> {
+        let idx = self.tail_index()?;
+        if f(&self.data[idx]) {
+            self.remove_index(idx)
+        } else {
+            None
+        }
     }
 
     /// Consumes the `Beap` and returns a vector in sorted
@@ -410,8 +1268,238 @@ impl<T: Ord> Beap<T> {
         self.data
     }
 
+    /// Consumes the `Beap` and returns a vector in sorted
+    /// (descending) order, reusing the underlying buffer.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    ///
+    /// let mut beap = Beap::from(vec![1, 2, 4, 5, 7]);
+    /// beap.push(6);
+    /// beap.push(3);
+    ///
+    /// let vec = beap.into_sorted_vec_desc();
+    /// assert_eq!(vec, [7, 6, 5, 4, 3, 2, 1]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*nlog(n)*)
+    ///
+    /// Inside, `Vec::sort_unstable_by` is used.
+    pub fn into_sorted_vec_desc(mut self) -> Vec<T> {
+        self.data.sort_unstable_by(|x, y| y.cmp(x));
+        self.data
+    }
+
+    /// Consumes the `Beap` and splits its elements around `pivot` into two sorted vectors:
+    /// the elements less than or equal to `pivot` in ascending order,
+    /// and the elements greater than `pivot` in descending order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    ///
+    /// let beap = Beap::from([5, 1, 8, 3, 3, 9]);
+    /// let (below, above) = beap.into_split_sorted(&3);
+    ///
+    /// assert_eq!(below, [1, 3, 3]);
+    /// assert_eq!(above, [9, 8, 5]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*nlog(n)*)
+    pub fn into_split_sorted(self, pivot: &T) -> (Vec<T>, Vec<T>) {
+        let mut above = self.into_sorted_vec_desc();
+        let mut below = above.split_off(above.partition_point(|x| x > pivot));
+        below.reverse();
+        (below, above)
+    }
+
+    /// Returns the `k`-th largest item in the beap (1-indexed), or `None` if
+    /// `k == 0` or `k` is greater than the length of the beap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([3, 8, 1, 6, 4]);
+    ///
+    /// assert_eq!(beap.kth_largest(1), Some(&8));
+    /// assert_eq!(beap.kth_largest(3), Some(&4));
+    /// assert_eq!(beap.kth_largest(5), Some(&1));
+    /// assert_eq!(beap.kth_largest(0), None);
+    /// assert_eq!(beap.kth_largest(6), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Expected *O*(min(*k*^2, *n*)): every item of the first `k` blocks has
+    /// at most `k - 1` ancestors, so only these blocks take part in the selection.
+    pub fn kth_largest(&self, k: usize) -> Option<&T> {
+        if k == 0 || k > self.len() {
+            return None;
+        }
+
+        let end = match self.span(k.min(self.height)) {
+            Some((_, end)) => (end + 1).min(self.len()),
+            None => self.len(),
+        };
+        Some(self.select(end, end - k))
+    }
+
+    /// Returns the `k`-th smallest item in the beap (1-indexed), or `None` if
+    /// `k == 0` or `k` is greater than the length of the beap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([3, 8, 1, 6, 4]);
+    ///
+    /// assert_eq!(beap.kth_smallest(1), Some(&1));
+    /// assert_eq!(beap.kth_smallest(3), Some(&4));
+    /// assert_eq!(beap.kth_smallest(5), Some(&8));
+    /// assert_eq!(beap.kth_smallest(0), None);
+    /// assert_eq!(beap.kth_smallest(6), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Expected *O*(*n*).
+    pub fn kth_smallest(&self, k: usize) -> Option<&T> {
+        if k == 0 || k > self.len() {
+            return None;
+        }
+        Some(self.select(self.len(), k - 1))
+    }
+
+    /// Sorts the underlying vector in descending order and returns the item
+    /// at position `n` (0-indexed) of the ascending order, or `None` if `n` is out of bounds.
+    ///
+    /// This is not a selection algorithm: the whole vector is sorted, which is
+    /// a valid beap layout, and the returned item lies at position `len - 1 - n`.
+    /// Sorting pays off when several positions are looked up in a row, since
+    /// an already sorted vector is sorted again in linear time.
+    /// Use [`kth_smallest`](Beap::kth_smallest) to find a single item in expected
+    /// *O*(*n*) without reordering the beap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([3, 8, 1, 6, 4]);
+    ///
+    /// assert_eq!(beap.select_nth(0), Some(&1));
+    /// assert_eq!(beap.select_nth(3), Some(&6));
+    /// assert_eq!(beap.select_nth(5), None);
+    /// assert_eq!(beap.as_slice(), &[8, 6, 4, 3, 1]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)), or *O*(*n*) if the vector is already sorted.
+    pub fn select_nth(&mut self, n: usize) -> Option<&T> {
+        if n >= self.len() {
+            return None;
+        }
+        self.rebuild();
+        Some(&self.data[self.len() - 1 - n])
+    }
+
+    /// Returns the lower median of the beap, that is the item at index `(len - 1) / 2`
+    /// of the sorted order, or `None` if the beap is empty.
+    ///
+    /// The item is found by selection: nothing is cloned and the beap is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([5, 1, 4, 2, 3]);
+    /// assert_eq!(beap.median(), Some(&3));
+    ///
+    /// beap.push(6);
+    /// assert_eq!(beap.median(), Some(&3));
+    ///
+    /// beap.clear();
+    /// assert_eq!(beap.median(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Expected *O*(*n*).
+    pub fn median(&self) -> Option<&T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.select(self.len(), (self.len() - 1) / 2))
+        }
+    }
+
+    /// Shrinks the beap to its `k` greatest items, dropping the rest.
+    ///
+    /// If `k` is greater or equal to the beap's current length, this has no effect.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([5, 1, 4, 2, 3]);
+    ///
+    /// beap.truncate_to_largest(2);
+    /// assert_eq!(beap.into_sorted_vec(), vec![4, 5]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Expected *O*(*n* + *k*log(*k*)).
+    pub fn truncate_to_largest(&mut self, k: usize) {
+        if k >= self.len() {
+            return;
+        }
+        if k > 0 {
+            self.data.select_nth_unstable_by(k - 1, |x, y| y.cmp(x));
+        }
+        self.data.truncate(k);
+        self.rebuild();
+    }
+
+    /// Returns the `n`-th smallest (0-indexed) item among the first `end` items
+    /// of the underlying vector without reordering it.
+    fn select(&self, end: usize, n: usize) -> &T {
+        let mut refs: Vec<&T> = self.data[..end].iter().collect();
+        refs.select_nth_unstable(n).1
+    }
+
     /// Changing the current element with its least priority parent until the beap property is restored
-    fn siftup(&mut self, mut pos: usize, mut block: usize) {
+    fn siftup(&mut self, pos: usize, block: usize) {
+        self.siftup_with(pos, block, &mut |_, _| {});
+    }
+
+    /// Same as `siftup`, but reports every swap of two positions to `on_swap`.
+    fn siftup_with<F>(&mut self, mut pos: usize, mut block: usize, on_swap: &mut F)
+    where
+        F: FnMut(usize, usize),
+    {
         let (mut start, _) = match self.span(block) {
             Some(idxs) => idxs,
             None => return,
@@ -446,6 +1534,7 @@ impl<T: Ord> Beap<T> {
             }
 
             self.data.swap(pos, parent);
+            on_swap(pos, parent);
             pos = parent;
             start = prev_start;
             block -= 1;
@@ -454,7 +1543,15 @@ impl<T: Ord> Beap<T> {
 
     /// Sift down in time O(sqrt(2N)).
     /// Swap the element with its largest child until the heap property is restored.
-    pub(crate) fn siftdown(&mut self, mut pos: usize, mut block: usize) {
+    pub(crate) fn siftdown(&mut self, pos: usize, block: usize) {
+        self.siftdown_with(pos, block, &mut |_, _| {});
+    }
+
+    /// Same as `siftdown`, but reports every swap of two positions to `on_swap`.
+    fn siftdown_with<F>(&mut self, mut pos: usize, mut block: usize, on_swap: &mut F)
+    where
+        F: FnMut(usize, usize),
+    {
         let (mut start, _) = match self.span(block) {
             Some(idxs) => idxs,
             None => return,
@@ -477,28 +1574,101 @@ impl<T: Ord> Beap<T> {
             if self.data[pos] >= self.data[child] {
                 break; // The beap property is met.
             }
-
-            self.data.swap(pos, child);
-            block += 1;
-            start = next_start;
-            pos = child;
+
+            self.data.swap(pos, child);
+            on_swap(pos, child);
+            block += 1;
+            start = next_start;
+            pos = child;
+        }
+    }
+
+    /// Restore the beap property (after changing the `pos` element).
+    pub(crate) fn repair(&mut self, pos: usize) {
+        self.repair_with(pos, &mut |_, _| {});
+    }
+
+    /// Restore the beap property after changing the elements at several positions.
+    pub(crate) fn repair_many(&mut self, positions: &[usize]) {
+        // Repair the deepest positions first. A repair may move other changed items
+        // around, so every position touched by a swap is checked once more.
+        let mut pending = positions.to_vec();
+        pending.sort_unstable();
+        while let Some(pos) = pending.pop() {
+            self.repair_with(pos, &mut |a, b| pending.extend([a, b]));
+        }
+    }
+
+    /// Same as `repair`, but reports every swap of two positions to `on_swap`.
+    pub(crate) fn repair_with<F>(&mut self, pos: usize, on_swap: &mut F)
+    where
+        F: FnMut(usize, usize),
+    {
+        if pos == 0 {
+            self.siftdown_with(pos, 1, on_swap);
+        } else {
+            let b = block_of(pos);
+            self.siftup_with(pos, b, on_swap);
+            self.siftdown_with(pos, b, on_swap);
+        }
+    }
+
+    /// Returns `true` if the beap is well-formed: every element is greater than or equal
+    /// to its children and the height matches the length.
+    ///
+    /// A beap built and modified through its public API is always valid,
+    /// so this is mostly useful in tests and debug assertions.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([3, 1, 4, 1, 5]);
+    /// assert!(beap.is_valid());
+    ///
+    /// beap.push(9);
+    /// assert!(beap.is_valid());
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*).
+    pub fn is_valid(&self) -> bool {
+        if self.height != height_for(self.len()) {
+            return false;
+        }
+        for block in 1..self.height {
+            let (start, end) = self.span(block).unwrap();
+            let (next_start, _) = self.span(block + 1).unwrap();
+            for pos in start..=end {
+                let child = next_start + pos - start;
+                for c in child..(child + 2).min(self.len()) {
+                    if self.data[pos] < self.data[c] {
+                        return false;
+                    }
+                }
+            }
         }
+        true
     }
 
-    /// Restore the beap property (after changing the `pos` element).
-    pub(crate) fn repair(&mut self, pos: usize) {
-        if pos == 0 {
-            self.siftdown(pos, 1);
-        } else {
-            let b = ((2 * (pos + 1)) as f64).sqrt().round() as usize;
-            self.siftup(pos, b);
-            self.siftdown(pos, b);
-        }
+    /// Restore the beap property after arbitrary changes of the underlying vector.
+    ///
+    /// Sorting in descending order gives a valid layout, after which only the height
+    /// has to be recomputed.
+    pub(crate) fn rebuild(&mut self) {
+        self.data.sort_unstable_by(|x, y| y.cmp(x));
+        self.height = height_for(self.data.len());
     }
 
     /// Find the index of an element with given value
     /// or return `None` if such element does not exist.
     ///
+    /// The value may be any borrowed form of the beap's element type,
+    /// but the ordering on the borrowed form *must* match the ordering on the element type.
+    ///
     /// Time complexity: *O(sqrt(2n))*.
     ///
     /// # Algorithm
@@ -523,17 +1693,17 @@ impl<T: Ord> Beap<T> {
     /// (the last element of the inner vector).
     ///
     /// 1) If the priority of the desired element is greater than that
-    ///     of the element in the current position, then move to the left along the line.
+    ///    of the element in the current position, then move to the left along the line.
     ///
     /// 2) If the priority of the desired element is less than that of the element
-    ///     in the current position, then move it down the column,
+    ///    in the current position, then move it down the column,
     ///
     /// 3) and if there is no element at the bottom, then move down and to the left
-    ///     (= left on the last layer of the heap).
+    ///    (= left on the last layer of the heap).
     ///
     /// 4) As soon as we find an element with equal val priority, we return its index,
-    ///     and if we find ourselves in the left in the lower corner and the value in it
-    ///     is not equal to val, so the desired element does not exist and it's time to return None.
+    ///    and if we find ourselves in the left in the lower corner and the value in it
+    ///    is not equal to val, so the desired element does not exist and it's time to return None.
     ///
     /// # Example
     ///
@@ -549,8 +1719,19 @@ impl<T: Ord> Beap<T> {
     ///
     /// # Time complexity
     ///
-    /// *O*(sqrt(*2n*))
-    pub fn index(&self, val: &T) -> Option<usize> {
+    /// *O*(sqrt(*2n*)), or *O*(1) if `val` is greater than the largest element.
+    ///
+    /// There is no such shortcut for values less than the smallest element,
+    /// because finding it takes *O*(sqrt(*2n*)) as well.
+    pub fn index<Q>(&self, val: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if val > self.peek()?.borrow() {
+            return None; // Greater than every element.
+        }
+
         let (left_low, mut right_up) = self.span(self.height)?;
         let mut block = self.height;
 
@@ -561,19 +1742,20 @@ impl<T: Ord> Beap<T> {
 
         let mut pos = right_up;
         while pos != left_low {
-            if self.data[pos] == *val {
+            let item: &Q = self.data[pos].borrow();
+            if item == val {
                 return Some(pos);
             }
 
             let (start, _) = self.span(block).unwrap();
             let block_pos = pos - start;
 
-            if block > 1 && block_pos > 0 && *val > self.data[pos] {
+            if block > 1 && block_pos > 0 && val > item {
                 // Case 1: go to the left
                 let (prev_start, _) = self.span(block - 1).unwrap();
                 pos = prev_start + block_pos - 1;
                 block -= 1;
-            } else if *val < self.data[pos] && block < self.height {
+            } else if val < item && block < self.height {
                 let (next_start, _) = self.span(block + 1).unwrap();
                 if next_start + block_pos >= self.len() {
                     pos -= 1; // Case 3: Go left and down (diagonally).
@@ -589,13 +1771,42 @@ impl<T: Ord> Beap<T> {
             }
         }
 
-        if *val == self.data[left_low] {
+        if self.data[left_low].borrow() == val {
             Some(left_low)
         } else {
             None
         }
     }
 
+    /// Find the indexes of all the elements with given value.
+    ///
+    /// The indexes are returned in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use beap::Beap;
+    ///
+    /// let b = Beap::from([1, 2, 2, 3, 2]);
+    /// let idxs = b.find_all(&2);
+    ///
+    /// assert_eq!(idxs.len(), 3);
+    /// assert!(idxs.iter().all(|&i| b.get(i) == Some(&2)));
+    /// assert!(b.find_all(&4).is_empty());
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*).
+    pub fn find_all(&self, val: &T) -> Vec<usize> {
+        self.data
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| *x == val)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
     /// Remove an element at the specified position.
     ///
     /// If the passed index is greater than the max index of the beap, it returns `None`.
@@ -620,7 +1831,99 @@ impl<T: Ord> Beap<T> {
     ///
     /// ```
     pub fn remove_index(&mut self, pos: usize) -> Option<T> {
-        if pos > self.data.len() {
+        self.remove_index_with(pos, &mut |_, _| {})
+    }
+
+    /// Removes an element at the specified position and returns it together with
+    /// the final position of the last element, which is moved into the hole.
+    ///
+    /// The position is `None` if the removed element was the last one, so nothing was moved.
+    /// Returns `None` if the passed index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(beap.as_slice(), &[6, 5, 4, 3, 2, 1]);
+    ///
+    /// let (item, moved) = beap.remove_index_reported(1).unwrap();
+    /// assert_eq!(item, 5);
+    /// assert_eq!(beap.get(moved.unwrap()), Some(&1));
+    ///
+    /// let last = beap.len() - 1;
+    /// assert_eq!(beap.remove_index_reported(last).unwrap().1, None);
+    /// assert_eq!(beap.remove_index_reported(10), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*))
+    pub fn remove_index_reported(&mut self, pos: usize) -> Option<(T, Option<usize>)> {
+        let mut moved = None;
+        let item = self.remove_index_with(pos, &mut |a, b| match moved {
+            // The first report is the move of the last element into the hole at `a`.
+            None => moved = Some(a),
+            Some(m) if m == a => moved = Some(b),
+            Some(m) if m == b => moved = Some(a),
+            _ => {}
+        })?;
+        Some((item, moved))
+    }
+
+    /// Removes the elements at the specified positions and returns them
+    /// in the order of ascending position.
+    ///
+    /// Positions out of bounds and repeated positions are ignored.
+    /// All the positions refer to the layout before the call,
+    /// and the beap is rebuilt only once at the end.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(beap.as_slice(), &[6, 5, 4, 3, 2, 1]);
+    ///
+    /// assert_eq!(beap.remove_indices(&[4, 1, 4, 10]), vec![5, 2]);
+    /// assert_eq!(beap.into_sorted_vec(), vec![1, 3, 4, 6]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n* + *k*) to remove the elements, where *k* is the number of positions,
+    /// plus *O*(*n*log(*n*)) to rebuild the beap if any element has been removed.
+    pub fn remove_indices(&mut self, positions: &[usize]) -> Vec<T> {
+        let mut marked = alloc::vec![false; self.len()];
+        for &pos in positions {
+            if let Some(m) = marked.get_mut(pos) {
+                *m = true;
+            }
+        }
+
+        let mut marks = marked.into_iter();
+        let removed: Vec<T> = self
+            .data
+            .extract_if(.., |_| marks.next().unwrap_or(false))
+            .collect();
+        if !removed.is_empty() {
+            self.rebuild();
+        }
+        removed
+    }
+
+    /// Same as `remove_index`, but reports every swap of two positions to `on_swap`.
+    /// Moving the last element in place of the removed one is reported
+    /// as a swap with the position just past the end.
+    pub(crate) fn remove_index_with<F>(&mut self, pos: usize, on_swap: &mut F) -> Option<T>
+    where
+        F: FnMut(usize, usize),
+    {
+        if pos >= self.data.len() {
             return None;
         }
 
@@ -632,8 +1935,9 @@ impl<T: Ord> Beap<T> {
                     }
 
                     if pos != self.len() {
-                        std::mem::swap(&mut item, &mut self.data[pos]);
-                        self.repair(pos);
+                        core::mem::swap(&mut item, &mut self.data[pos]);
+                        on_swap(pos, self.len());
+                        self.repair_with(pos, on_swap);
                     }
                 }
             } else {
@@ -668,10 +1972,104 @@ impl<T: Ord> Beap<T> {
     ///
     /// Operation can be done in *O*(n*log(n)),
     /// where *n* = self.len() + other.len().
+    /// If both beaps are sorted in descending order, which is the case e.g. right
+    /// after building them from a vector, it takes only *O*(*n*).
     pub fn append(&mut self, other: &mut Self) {
         other.height = 0;
-        self.data.append(&mut other.data);
-        self.data.sort_unstable_by(|x, y| y.cmp(x));
+        self.append_vec(&mut other.data);
+    }
+
+    /// Moves all the elements of several beaps into `self`, leaving them empty.
+    ///
+    /// Unlike repeated calls to [`append`](Beap::append), the beap is rebuilt only once.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    ///
+    /// let mut beap = Beap::from([1, 4]);
+    /// let mut others = [Beap::from([3, 9]), Beap::new(), Beap::from([2])];
+    ///
+    /// beap.append_many(&mut others);
+    /// assert!(others.iter().all(|b| b.is_empty()));
+    /// assert_eq!(beap.into_sorted_vec(), [1, 2, 3, 4, 9]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)), where *n* is the total number of elements.
+    pub fn append_many(&mut self, others: &mut [Beap<T>]) {
+        let additional = others.iter().map(Beap::len).sum();
+        if additional == 0 {
+            return;
+        }
+
+        self.data.reserve(additional);
+        for other in others {
+            other.height = 0;
+            self.data.append(&mut other.data);
+        }
+        self.rebuild();
+    }
+
+    /// Consumes several beaps and moves all their elements into `self`.
+    ///
+    /// Like [`append_many`](Beap::append_many), the beap is rebuilt only once,
+    /// but the beaps can come from any iterator.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    ///
+    /// let mut beap = Beap::from([1, 4]);
+    /// let others = vec![Beap::from([3, 9]), Beap::new(), Beap::from([2])];
+    ///
+    /// beap.absorb(others);
+    /// assert_eq!(beap.into_sorted_vec(), [1, 2, 3, 4, 9]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)), where *n* is the total number of elements.
+    pub fn absorb<I: IntoIterator<Item = Beap<T>>>(&mut self, others: I) {
+        let len = self.len();
+        for other in others {
+            self.data.extend(other.data);
+        }
+        if self.len() > len {
+            self.rebuild();
+        }
+    }
+
+    /// Consumes two beaps and merges them into one.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    ///
+    /// let a = Beap::from([-10, 1, 2, 3, 3]);
+    /// let b = Beap::from([-20, 5, 43]);
+    ///
+    /// let merged = Beap::merge(a, b);
+    /// assert_eq!(merged.into_sorted_vec(), [-20, -10, 1, 2, 3, 3, 5, 43]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The same as [`append`](Beap::append): *O*(*n*) if both beaps
+    /// are sorted in descending order, *O*(*n*log(*n*)) otherwise.
+    pub fn merge(mut a: Beap<T>, mut b: Beap<T>) -> Beap<T> {
+        a.append(&mut b);
+        a
     }
 
     /// Moves all the elements of `other` into `self`, leaving `other` empty.
@@ -696,9 +2094,240 @@ impl<T: Ord> Beap<T> {
     ///
     /// Operation can be done in *O*(n*log(n)),
     /// where *n* = self.len() + other.len().
+    /// If both the beap and `other` are sorted in descending order,
+    /// it takes only *O*(*n*).
     pub fn append_vec(&mut self, other: &mut Vec<T>) {
-        self.data.append(other);
+        if other.is_empty() {
+            return;
+        }
+
+        // Sorting is linear for the already sorted parts,
+        // after which the two runs are merged.
+        other.sort_unstable_by(|x, y| y.cmp(x));
+        self.merge_sorted_desc(other);
+    }
+
+    /// Moves all the elements of a vector sorted in descending order into `self`,
+    /// leaving the vector empty.
+    ///
+    /// Unlike [`append_vec`](Beap::append_vec), `other` is not sorted again.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `other` is not sorted in descending order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    ///
+    /// let mut beap = Beap::from([-10, 1, 2, 3, 3]);
+    ///
+    /// let mut v = vec![43, 5, -20];
+    /// beap.append_sorted_desc_vec(&mut v);
+    ///
+    /// assert_eq!(beap.into_sorted_vec(), [-20, -10, 1, 2, 3, 3, 5, 43]);
+    /// assert!(v.is_empty());
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*) if the beap is sorted in descending order,
+    /// which is the case e.g. right after building it from a vector,
+    /// otherwise *O*(*n*log(*n*)), where *n* = self.len() + other.len().
+    pub fn append_sorted_desc_vec(&mut self, other: &mut Vec<T>) {
+        debug_assert!(
+            other.windows(2).all(|w| w[0] >= w[1]),
+            "the vector is not sorted in descending order"
+        );
+        if other.is_empty() {
+            return;
+        }
+        self.merge_sorted_desc(other);
+    }
+
+    /// Merges a vector sorted in descending order into `self`, leaving the vector empty.
+    ///
+    /// The merge runs from the back inside `self`'s own buffer, so no allocation
+    /// happens if `self` already has room for `other.len()` more elements.
+    fn merge_sorted_desc(&mut self, other: &mut Vec<T>) {
+        /// Fills the gap left in `dst` with the unmerged rest of `src` on drop,
+        /// so a panicking comparison leaves every element owned exactly once
+        /// and the height consistent with the length.
+        struct Hole<'a, T> {
+            dst: &'a mut Beap<T>,
+            src: *const T,
+            left: usize,
+            right: usize,
+            total: usize,
+        }
+
+        impl<T> Drop for Hole<'_, T> {
+            fn drop(&mut self) {
+                // SAFETY: `dst[left..left + right]` is the only unoccupied part of the
+                // first `total` slots, and `src[..right]` are exactly the elements
+                // that have not been moved yet.
+                unsafe {
+                    let dst = self.dst.data.as_mut_ptr();
+                    ptr::copy_nonoverlapping(self.src, dst.add(self.left), self.right);
+                    self.dst.data.set_len(self.total);
+                }
+                self.dst.height = height_for(self.total);
+            }
+        }
+
         self.data.sort_unstable_by(|x, y| y.cmp(x));
+
+        let (n, m) = (self.data.len(), other.len());
+        self.data.reserve(m);
+
+        // SAFETY: `other` gives up ownership of its elements by setting its length
+        // to zero, its buffer stays alive until the end of the function. `self.data`
+        // has room for `n + m` elements, and every slot `k = left + right - 1` is
+        // past both unmerged runs, so each element is moved into an empty slot.
+        unsafe {
+            other.set_len(0);
+            let mut hole = Hole {
+                dst: self,
+                src: other.as_ptr(),
+                left: n,
+                right: m,
+                total: n + m,
+            };
+            while hole.left > 0 && hole.right > 0 {
+                let dst = hole.dst.data.as_mut_ptr();
+                let k = hole.left + hole.right - 1;
+                let a = dst.add(hole.left - 1);
+                let b = hole.src.add(hole.right - 1);
+                if *a < *b {
+                    ptr::copy_nonoverlapping(a, dst.add(k), 1);
+                    hole.left -= 1;
+                } else {
+                    ptr::copy_nonoverlapping(b, dst.add(k), 1);
+                    hole.right -= 1;
+                }
+            }
+        }
+    }
+
+    /// Splits the beap into two by a threshold value.
+    ///
+    /// Returns a beap containing all the elements greater than `threshold`,
+    /// leaving the elements less than or equal to `threshold` in `self`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    ///
+    /// let mut a = Beap::from([1, 5, 2, 4, 3]);
+    /// let b = a.split_off(&3);
+    ///
+    /// assert_eq!(a.into_sorted_vec(), [1, 2, 3]);
+    /// assert_eq!(b.into_sorted_vec(), [4, 5]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*) to partition the elements plus *O*(*n*log(*n*)) to rebuild both beaps.
+    pub fn split_off(&mut self, threshold: &T) -> Beap<T> {
+        let greater: Vec<T> = self.data.extract_if(.., |x| &*x > threshold).collect();
+        self.rebuild();
+        Beap::from(greater)
+    }
+
+    /// Removes the specified range of positions of the underlying vector
+    /// and returns the removed elements in arbitrary order.
+    ///
+    /// The remaining elements are re-heapified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the beap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 2, 3, 4, 5]);
+    /// assert_eq!(beap.as_slice(), &[5, 4, 3, 2, 1]);
+    ///
+    /// let mut drained = beap.drain_range(1..3);
+    /// drained.sort();
+    /// assert_eq!(drained, [3, 4]);
+    /// assert_eq!(beap.into_sorted_vec(), [1, 2, 5]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)) to rebuild the beap, or *O*(1) if the range is empty.
+    pub fn drain_range<R: RangeBounds<usize>>(&mut self, range: R) -> Vec<T> {
+        let drained: Vec<T> = self.data.drain(range).collect();
+        if !drained.is_empty() {
+            self.rebuild();
+        }
+        drained
+    }
+}
+
+impl<T: Ord + Clone> Beap<T> {
+    /// Returns a vector of all the items in sorted (ascending) order,
+    /// leaving the beap untouched.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([4, 1, 3, 2]);
+    ///
+    /// assert_eq!(beap.to_sorted_vec(), vec![1, 2, 3, 4]);
+    /// assert_eq!(beap.len(), 4);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)).
+    pub fn to_sorted_vec(&self) -> Vec<T> {
+        self.clone().into_sorted_vec()
+    }
+
+    /// Returns the `n` smallest items of the beap in ascending order.
+    ///
+    /// If `n` is greater than the length of the beap, all items are returned.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([5, 1, 4, 2, 3]);
+    ///
+    /// assert_eq!(beap.nsmallest(3), vec![1, 2, 3]);
+    /// assert_eq!(beap.nsmallest(0), vec![]);
+    /// assert_eq!(beap.nsmallest(10), vec![1, 2, 3, 4, 5]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*len* + *n*log(*n*)), only the returned items are cloned.
+    pub fn nsmallest(&self, n: usize) -> Vec<T> {
+        let mut refs: Vec<&T> = self.data.iter().collect();
+        if n < refs.len() {
+            refs.select_nth_unstable(n);
+            refs.truncate(n);
+        }
+        refs.sort_unstable();
+        refs.into_iter().cloned().collect()
     }
 }
 
@@ -728,6 +2357,37 @@ impl<T> Beap<T> {
         self.data.first()
     }
 
+    /// Returns the index of the greatest item in the beap, or `None` if it is empty.
+    ///
+    /// The greatest item is always at the top, so this is `Some(0)` for a non-empty beap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::new();
+    /// assert_eq!(beap.position_of_max(), None);
+    ///
+    /// beap.push(1);
+    /// beap.push(5);
+    /// assert_eq!(beap.position_of_max(), Some(0));
+    /// assert_eq!(beap.remove_index(0), Some(5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn position_of_max(&self) -> Option<usize> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(0)
+        }
+    }
+
     /// Get an item at the specified position.
     ///
     /// Returns `None` if the `pos` goes beyond the beap.
@@ -760,3 +2420,35 @@ impl<T> Beap<T> {
         }
     }
 }
+
+/// Number of the block containing the element at position `pos`,
+/// without any floating-point arithmetic.
+pub(crate) fn block_of(pos: usize) -> usize {
+    height_for(pos + 1)
+}
+
+/// Returns the height of a beap of `len` elements, i.e. the number of its blocks.
+///
+/// The block `b` holds `b` elements, so this is the smallest `h` with `h * (h + 1) / 2 >= len`.
+/// It is computed in exact integer arithmetic.
+///
+/// # Examples
+///
+/// ```
+/// use beap::height_for;
+///
+/// assert_eq!(height_for(0), 0);
+/// assert_eq!(height_for(1), 1);
+/// assert_eq!(height_for(3), 2);
+/// assert_eq!(height_for(4), 3);
+/// assert_eq!(height_for(5050), 100);
+/// ```
+pub fn height_for(len: usize) -> usize {
+    // `isqrt(2 * len)` is either the answer or one less than it.
+    let h = len.saturating_mul(2).isqrt();
+    if h * (h + 1) / 2 < len {
+        h + 1
+    } else {
+        h
+    }
+}
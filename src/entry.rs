@@ -0,0 +1,147 @@
+//! Entry API for conditional updates.
+use super::{Beap, PosMut};
+
+/// A view into a single value of a [`Beap`], which may either be present or absent.
+///
+/// This `enum` is created by the [`entry`] method on [`Beap`].
+///
+/// [`entry`]: Beap::entry
+#[derive(Debug)]
+pub enum Entry<'a, T: 'a + Ord> {
+    /// The value is present in the beap.
+    Occupied(OccupiedEntry<'a, T>),
+    /// The value is absent from the beap.
+    Vacant(VacantEntry<'a, T>),
+}
+
+/// A view into a value present in a [`Beap`]. It is part of the [`Entry`] enum.
+#[derive(Debug)]
+pub struct OccupiedEntry<'a, T: 'a + Ord> {
+    beap: &'a mut Beap<T>,
+    pos: usize,
+}
+
+/// A view into a value absent from a [`Beap`]. It is part of the [`Entry`] enum.
+#[derive(Debug)]
+pub struct VacantEntry<'a, T: 'a + Ord> {
+    beap: &'a mut Beap<T>,
+}
+
+impl<T: Ord> Beap<T> {
+    /// Gets the entry of the given value for in-place manipulation.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::{Beap, Entry};
+    /// let mut beap = Beap::from([(1, 'a'), (3, 'b')]);
+    ///
+    /// // Bump the priority of the present item...
+    /// if let Entry::Occupied(entry) = beap.entry(&(1, 'a')) {
+    ///     entry.get_mut().0 = 5;
+    /// }
+    /// assert_eq!(beap.peek(), Some(&(5, 'a')));
+    ///
+    /// // ...or push the absent one.
+    /// match beap.entry(&(2, 'c')) {
+    ///     Entry::Occupied(entry) => {
+    ///         entry.remove();
+    ///     }
+    ///     Entry::Vacant(entry) => entry.insert((2, 'c')),
+    /// }
+    /// assert_eq!(beap.len(), 3);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn entry(&mut self, val: &T) -> Entry<'_, T> {
+        match self.index(val) {
+            Some(pos) => Entry::Occupied(OccupiedEntry { beap: self, pos }),
+            None => Entry::Vacant(VacantEntry { beap: self }),
+        }
+    }
+}
+
+impl<'a, T: Ord> Entry<'a, T> {
+    /// Pushes `item` if the entry is vacant, and returns mutable access to the value.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 3]);
+    ///
+    /// *beap.entry(&1).or_insert(1) = 10;
+    /// *beap.entry(&2).or_insert(2) += 5;
+    /// assert_eq!(beap.into_sorted_vec(), vec![3, 7, 10]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn or_insert(self, item: T) -> PosMut<'a, T> {
+        match self {
+            Entry::Occupied(entry) => entry.get_mut(),
+            Entry::Vacant(entry) => {
+                let beap = entry.beap;
+                let mut pos = beap.len();
+                beap.push_with(item, &mut |a, b| {
+                    if a == pos {
+                        pos = b;
+                    } else if b == pos {
+                        pos = a;
+                    }
+                });
+                beap.get_mut(pos).unwrap()
+            }
+        }
+    }
+}
+
+impl<'a, T: Ord> OccupiedEntry<'a, T> {
+    /// Returns a reference to the value in the entry.
+    pub fn get(&self) -> &T {
+        &self.beap.data[self.pos]
+    }
+
+    /// Returns the position of the value in the underlying vector.
+    pub fn index(&self) -> usize {
+        self.pos
+    }
+
+    /// Converts the entry into mutable access to the value.
+    ///
+    /// The beap property is restored when the returned [`PosMut`] is dropped.
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)) to restore the heap property if the item has been changed.
+    pub fn get_mut(self) -> PosMut<'a, T> {
+        self.beap.get_mut(self.pos).unwrap()
+    }
+
+    /// Removes the value from the beap and returns it.
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn remove(self) -> T {
+        self.beap.remove_index(self.pos).unwrap()
+    }
+}
+
+impl<T: Ord> VacantEntry<'_, T> {
+    /// Pushes an item onto the beap.
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn insert(self, item: T) {
+        self.beap.push(item);
+    }
+}
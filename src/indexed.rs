@@ -0,0 +1,405 @@
+//! Beap with stable handles to its items.
+use super::Beap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+
+/// A stable handle to an item of an [`IndexedBeap`].
+///
+/// A key stays valid until its item is removed from the beap,
+/// no matter how the other items are moved around.
+/// Keys of removed items are never confused with the keys of new ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    slot: usize,
+    generation: usize,
+}
+
+/// A priority queue implemented with a bi-parental heap,
+/// which hands out a stable [`Key`] for every inserted item.
+///
+/// Unlike the positions accepted by [`Beap::get_mut`] or [`Beap::remove_index`],
+/// keys do not change as the beap is reordered, so they can be held for a long time,
+/// e.g. to change the priority of a vertex in a graph algorithm.
+///
+/// This will be a max-heap.
+///
+/// # Examples
+///
+/// ```
+/// use beap::IndexedBeap;
+///
+/// let mut beap = IndexedBeap::new();
+/// let a = beap.insert(1);
+/// let b = beap.insert(5);
+/// let c = beap.insert(3);
+///
+/// assert_eq!(beap.peek(), Some((b, &5)));
+///
+/// beap.change_priority(a, 10);
+/// assert_eq!(beap.pop(), Some((a, 10)));
+///
+/// assert_eq!(beap.remove(b), Some(5));
+/// assert_eq!(beap.get(b), None);
+/// assert_eq!(beap.get(c), Some(&3));
+/// ```
+pub struct IndexedBeap<T> {
+    beap: Beap<Entry<T>>,
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+    /// Scratch buffer for the positions moved by the current operation,
+    /// it is always empty between calls.
+    touched: Vec<usize>,
+}
+
+impl<T: fmt::Debug> fmt::Debug for IndexedBeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IndexedBeap")
+            .field("beap", &self.beap)
+            .field("slots", &self.slots)
+            .field("free", &self.free)
+            .finish()
+    }
+}
+
+impl<T: Clone> Clone for IndexedBeap<T> {
+    fn clone(&self) -> Self {
+        IndexedBeap {
+            beap: self.beap.clone(),
+            slots: self.slots.clone(),
+            free: self.free.clone(),
+            touched: Vec::new(),
+        }
+    }
+}
+
+/// Item of the beap together with the slot keeping its position.
+#[derive(Debug, Clone)]
+struct Entry<T> {
+    item: T,
+    slot: usize,
+}
+
+impl<T: PartialEq> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.item == other.item
+    }
+}
+
+impl<T: Eq> Eq for Entry<T> {}
+
+impl<T: PartialOrd> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.item.partial_cmp(&other.item)
+    }
+}
+
+impl<T: Ord> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.item.cmp(&other.item)
+    }
+}
+
+/// Current position of an item, or `None` if the slot is free.
+#[derive(Debug, Clone)]
+struct Slot {
+    generation: usize,
+    pos: Option<usize>,
+}
+
+impl<T> Default for IndexedBeap<T> {
+    fn default() -> Self {
+        IndexedBeap::new()
+    }
+}
+
+impl<T> IndexedBeap<T> {
+    /// Creates an empty `IndexedBeap`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::IndexedBeap;
+    /// let mut beap = IndexedBeap::new();
+    /// beap.insert(4);
+    /// assert_eq!(beap.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn new() -> IndexedBeap<T> {
+        IndexedBeap {
+            beap: Beap::new(),
+            slots: Vec::new(),
+            free: Vec::new(),
+            touched: Vec::new(),
+        }
+    }
+
+    /// Returns the length of the beap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::IndexedBeap;
+    /// let mut beap = IndexedBeap::new();
+    /// beap.insert(1);
+    /// beap.insert(3);
+    ///
+    /// assert_eq!(beap.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.beap.len()
+    }
+
+    /// Checks if the beap is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::IndexedBeap;
+    /// let mut beap = IndexedBeap::new();
+    /// assert!(beap.is_empty());
+    ///
+    /// beap.insert(3);
+    /// assert!(!beap.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.beap.is_empty()
+    }
+
+    /// Returns the greatest item in the beap together with its key,
+    /// or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::IndexedBeap;
+    /// let mut beap = IndexedBeap::new();
+    /// assert_eq!(beap.peek(), None);
+    ///
+    /// beap.insert(1);
+    /// let key = beap.insert(5);
+    /// beap.insert(2);
+    /// assert_eq!(beap.peek(), Some((key, &5)));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(1).
+    pub fn peek(&self) -> Option<(Key, &T)> {
+        self.beap.peek().map(|e| (self.key_of(e.slot), &e.item))
+    }
+
+    /// Returns the item with the given key, or `None` if it has been removed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::IndexedBeap;
+    /// let mut beap = IndexedBeap::new();
+    /// let key = beap.insert(4);
+    /// assert_eq!(beap.get(key), Some(&4));
+    ///
+    /// beap.remove(key);
+    /// assert_eq!(beap.get(key), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(1).
+    pub fn get(&self, key: Key) -> Option<&T> {
+        self.pos(key).map(|pos| &self.beap.data[pos].item)
+    }
+
+    /// Returns `true` if the item with the given key is in the beap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::IndexedBeap;
+    /// let mut beap = IndexedBeap::new();
+    /// let key = beap.insert(4);
+    /// assert!(beap.contains_key(key));
+    ///
+    /// beap.pop();
+    /// assert!(!beap.contains_key(key));
+    /// ```
+    pub fn contains_key(&self, key: Key) -> bool {
+        self.pos(key).is_some()
+    }
+
+    /// Current position of the item with the given key.
+    fn pos(&self, key: Key) -> Option<usize> {
+        self.slots
+            .get(key.slot)
+            .filter(|slot| slot.generation == key.generation)
+            .and_then(|slot| slot.pos)
+    }
+
+    fn key_of(&self, slot: usize) -> Key {
+        Key {
+            slot,
+            generation: self.slots[slot].generation,
+        }
+    }
+
+    /// Records the positions of all the items moved by the last operation.
+    fn sync(&mut self) {
+        for pos in self.touched.drain(..) {
+            if let Some(entry) = self.beap.data.get(pos) {
+                self.slots[entry.slot].pos = Some(pos);
+            }
+        }
+    }
+}
+
+impl<T: Ord> IndexedBeap<T> {
+    /// Pushes an item onto the beap and returns its key.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::IndexedBeap;
+    /// let mut beap = IndexedBeap::new();
+    /// let a = beap.insert(3);
+    /// let b = beap.insert(5);
+    ///
+    /// assert_eq!(beap.len(), 2);
+    /// assert_eq!(beap.get(a), Some(&3));
+    /// assert_eq!(beap.peek(), Some((b, &5)));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn insert(&mut self, item: T) -> Key {
+        let pos = Some(self.len());
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.slots[slot].pos = pos;
+                slot
+            }
+            None => {
+                self.slots.push(Slot { generation: 0, pos });
+                self.slots.len() - 1
+            }
+        };
+
+        let touched = &mut self.touched;
+        self.beap
+            .push_with(Entry { item, slot }, &mut |a, b| touched.extend([a, b]));
+        self.sync();
+        self.key_of(slot)
+    }
+
+    /// Removes the greatest item from the beap and returns it together with its key,
+    /// or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::IndexedBeap;
+    /// let mut beap = IndexedBeap::new();
+    /// let a = beap.insert(1);
+    /// let b = beap.insert(3);
+    ///
+    /// assert_eq!(beap.pop(), Some((b, 3)));
+    /// assert_eq!(beap.pop(), Some((a, 1)));
+    /// assert_eq!(beap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn pop(&mut self) -> Option<(Key, T)> {
+        let key = self.peek()?.0;
+        self.remove(key).map(|item| (key, item))
+    }
+
+    /// Removes the item with the given key from the beap and returns it,
+    /// or `None` if it has already been removed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::IndexedBeap;
+    /// let mut beap = IndexedBeap::new();
+    /// let a = beap.insert(1);
+    /// let b = beap.insert(3);
+    ///
+    /// assert_eq!(beap.remove(a), Some(1));
+    /// assert_eq!(beap.remove(a), None);
+    /// assert_eq!(beap.peek(), Some((b, &3)));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn remove(&mut self, key: Key) -> Option<T> {
+        let pos = self.pos(key)?;
+        let slot = &mut self.slots[key.slot];
+        slot.pos = None;
+        slot.generation += 1;
+        self.free.push(key.slot);
+
+        let touched = &mut self.touched;
+        let entry = self
+            .beap
+            .remove_index_with(pos, &mut |a, b| touched.extend([a, b]));
+        self.sync();
+        entry.map(|e| e.item)
+    }
+
+    /// Replaces the item with the given key and returns the old one,
+    /// or `None` if it has been removed.
+    ///
+    /// The item may become either greater or less than before.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::IndexedBeap;
+    /// let mut beap = IndexedBeap::new();
+    /// let a = beap.insert(1);
+    /// let b = beap.insert(3);
+    ///
+    /// assert_eq!(beap.change_priority(a, 10), Some(1));
+    /// assert_eq!(beap.peek(), Some((a, &10)));
+    ///
+    /// assert_eq!(beap.change_priority(a, 0), Some(10));
+    /// assert_eq!(beap.peek(), Some((b, &3)));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn change_priority(&mut self, key: Key, item: T) -> Option<T> {
+        let pos = self.pos(key)?;
+        let old = core::mem::replace(&mut self.beap.data[pos].item, item);
+
+        let touched = &mut self.touched;
+        self.beap
+            .repair_with(pos, &mut |a, b| touched.extend([a, b]));
+        self.sync();
+        Some(old)
+    }
+}
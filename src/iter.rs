@@ -1,12 +1,20 @@
 //! Beap iterators.
 use super::Beap;
-use std::fmt;
-use std::iter::FusedIterator;
+use alloc::vec::{self, Vec};
+use core::fmt;
+use core::iter::FusedIterator;
+use core::mem;
+use core::slice;
 
 impl<T> Beap<T> {
     /// Returns an iterator visiting all values in the underlying vector, in
     /// arbitrary order.
     ///
+    /// The iterator is double-ended, but `iter().rev()` merely walks the underlying
+    /// vector backwards, which is no more sorted than the forward order.
+    /// Use [`iter_sorted`](Beap::iter_sorted) to visit the values in ascending order,
+    /// or `iter_sorted().rev()` for descending order.
+    ///
     /// # Examples
     ///
     /// Basic usage:
@@ -28,6 +36,28 @@ impl<T> Beap<T> {
         }
     }
 
+    /// Returns an iterator over the blocks of the beap, from the top one
+    /// containing the greatest item to the last, possibly incomplete, one.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from(vec![1, 2, 3, 4, 5]);
+    ///
+    /// let blocks: Vec<&[i32]> = beap.blocks().collect();
+    /// assert_eq!(blocks, vec![&[5][..], &[4, 3][..], &[2, 1][..]]);
+    /// ```
+    pub fn blocks(&self) -> Blocks<'_, T> {
+        Blocks {
+            rest: &self.data,
+            size: 1,
+            remaining: self.height,
+        }
+    }
+
     /// Clears the bi-parental heap, returning an iterator over the removed elements
     /// in arbitrary order. If the iterator is dropped before being fully
     /// consumed, it drops the remaining elements in arbitrary order.
@@ -35,6 +65,13 @@ impl<T> Beap<T> {
     /// The returned iterator keeps a mutable borrow on the beap to optimize
     /// its implementation.
     ///
+    /// The beap is emptied as soon as `drain` is called, not when the iterator
+    /// is dropped. If the iterator is leaked (e.g. with [`mem::forget`]),
+    /// the remaining elements are leaked too, but the beap is still left empty
+    /// and ready to use.
+    ///
+    /// [`mem::forget`]: core::mem::forget
+    ///
     /// # Examples
     ///
     /// Basic usage:
@@ -59,6 +96,149 @@ impl<T> Beap<T> {
     }
 }
 
+impl<T: Ord> Beap<T> {
+    /// Creates an iterator which uses a closure to determine if an element should be removed.
+    ///
+    /// If the closure returns `true`, the element is removed from the beap and yielded.
+    /// If the closure returns `false`, the element remains in the beap and will not be
+    /// yielded. Elements are visited in arbitrary order.
+    ///
+    /// If the iterator is dropped before being fully consumed, the remaining elements
+    /// stay in the beap. The beap property is restored when the iterator is dropped.
+    ///
+    /// The elements are moved out of the beap while the iterator is alive, so if
+    /// the `ExtractIf` value is leaked (e.g. with [`mem::forget`]), the beap is left
+    /// empty and the remaining elements are leaked too.
+    ///
+    /// [`mem::forget`]: core::mem::forget
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 2, 3, 4, 5, 6]);
+    ///
+    /// let mut odds: Vec<_> = beap.extract_if(|x| x % 2 == 1).collect();
+    /// odds.sort();
+    ///
+    /// assert_eq!(odds, vec![1, 3, 5]);
+    /// assert_eq!(beap.into_sorted_vec(), vec![2, 4, 6]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*) for the iteration plus *O*(*n*log(*n*)) to rebuild the beap on drop
+    /// if any element has been removed.
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        // Leak amplification: the beap stays empty and consistent
+        // until the elements are put back on drop.
+        let data = mem::take(&mut self.data);
+        let height = mem::replace(&mut self.height, 0);
+        ExtractIf {
+            beap: self,
+            data,
+            height,
+            pos: 0,
+            pred,
+            sift: false,
+        }
+    }
+
+    /// Consumes the `Beap` and returns an iterator which yields elements
+    /// in descending order, and in ascending order from the back.
+    ///
+    /// Both ends are popped lazily, so taking only a few items is cheaper than
+    /// [`into_sorted_vec`](Beap::into_sorted_vec).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([1, 2, 3, 4, 5]);
+    ///
+    /// let mut iter = beap.into_iter_sorted();
+    /// assert_eq!(iter.next(), Some(5));
+    /// assert_eq!(iter.next_back(), Some(1));
+    /// assert_eq!(iter.collect::<Vec<_>>(), vec![4, 3, 2]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)) for each yielded item.
+    pub fn into_iter_sorted(self) -> IntoIterSorted<T> {
+        IntoIterSorted { inner: self }
+    }
+
+    /// Clears the beap, returning an iterator over the removed elements
+    /// in descending order, and in ascending order from the back.
+    ///
+    /// The elements are popped lazily from both ends. If the iterator is dropped
+    /// before it is fully consumed, the remaining elements are dropped too.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 2, 3, 4, 5]);
+    ///
+    /// let mut drain = beap.drain_sorted();
+    /// assert_eq!(drain.next(), Some(5));
+    /// assert_eq!(drain.next_back(), Some(1));
+    /// drop(drain);
+    ///
+    /// assert!(beap.is_empty());
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)) for each yielded item.
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T> {
+        DrainSorted { inner: self }
+    }
+
+    /// Returns an iterator visiting all values in the beap in ascending order.
+    ///
+    /// The beap is not modified: the positions of the items are sorted instead,
+    /// which takes a single allocation of *n* indexes.
+    ///
+    /// The iterator is double-ended, and `iter_sorted().rev()` is guaranteed
+    /// to visit the values in descending order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([3, 1, 4, 1, 5]);
+    ///
+    /// assert_eq!(beap.iter_sorted().collect::<Vec<_>>(), vec![&1, &1, &3, &4, &5]);
+    /// assert_eq!(beap.iter_sorted().rev().collect::<Vec<_>>(), vec![&5, &4, &3, &1, &1]);
+    /// assert_eq!(beap.len(), 5);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)) to create the iterator, then *O*(1) for each item.
+    pub fn iter_sorted(&self) -> IterSorted<'_, T> {
+        let mut positions: Vec<usize> = (0..self.len()).collect();
+        positions.sort_unstable_by(|&i, &j| self.data[i].cmp(&self.data[j]));
+        IterSorted {
+            beap: self,
+            positions: positions.into_iter(),
+        }
+    }
+}
+
 impl<T> IntoIterator for Beap<T> {
     type Item = T;
     type IntoIter = IntoIter<T>;
@@ -124,7 +304,27 @@ impl<'a, T> IntoIterator for &'a Beap<T> {
 /// [`iter`]: Beap::iter
 #[derive(Clone)]
 pub struct Iter<'a, T: 'a> {
-    iter: std::slice::Iter<'a, T>,
+    iter: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    /// Returns the remaining items of this iterator as a slice
+    /// of the underlying vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([1, 2, 3]);
+    /// let mut iter = beap.iter();
+    ///
+    /// assert_eq!(iter.as_slice(), &[3, 2, 1]);
+    /// let _ = iter.next().unwrap();
+    /// assert_eq!(iter.as_slice(), &[2, 1]);
+    /// ```
+    pub fn as_slice(&self) -> &'a [T] {
+        self.iter.as_slice()
+    }
 }
 
 impl<T: fmt::Debug> fmt::Debug for Iter<'_, T> {
@@ -161,6 +361,50 @@ impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
 
 impl<T> FusedIterator for Iter<'_, T> {}
 
+/// An iterator over the blocks of a `Beap`.
+///
+/// This `struct` is created by [`Beap::blocks()`]. See its
+/// documentation for more.
+///
+/// [`blocks`]: Beap::blocks
+#[derive(Clone)]
+pub struct Blocks<'a, T: 'a> {
+    rest: &'a [T],
+    size: usize,
+    remaining: usize,
+}
+
+impl<T: fmt::Debug> fmt::Debug for Blocks<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Blocks").field(&self.rest).finish()
+    }
+}
+
+impl<'a, T> Iterator for Blocks<'a, T> {
+    type Item = &'a [T];
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a [T]> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let (block, rest) = self.rest.split_at(self.size.min(self.rest.len()));
+        self.rest = rest;
+        self.size += 1;
+        self.remaining -= 1;
+        Some(block)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Blocks<'_, T> {}
+
+impl<T> FusedIterator for Blocks<'_, T> {}
+
 /// An owning iterator over the elements of a `Beap`.
 ///
 /// This `struct` is created by [`Beap::into_iter()`]
@@ -170,7 +414,7 @@ impl<T> FusedIterator for Iter<'_, T> {}
 /// [`IntoIterator`]: core::iter::IntoIterator
 #[derive(Clone)]
 pub struct IntoIter<T> {
-    iter: std::vec::IntoIter<T>,
+    iter: vec::IntoIter<T>,
 }
 
 impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
@@ -188,6 +432,11 @@ impl<T> Iterator for IntoIter<T> {
     fn next(&mut self) -> Option<T> {
         self.iter.next()
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
 }
 
 impl<T> DoubleEndedIterator for IntoIter<T> {
@@ -197,8 +446,136 @@ impl<T> DoubleEndedIterator for IntoIter<T> {
     }
 }
 
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
 impl<T> FusedIterator for IntoIter<T> {}
 
+/// An iterator over the elements of a `Beap` in ascending order.
+///
+/// This `struct` is created by [`Beap::iter_sorted()`]. See its
+/// documentation for more.
+pub struct IterSorted<'a, T: 'a> {
+    beap: &'a Beap<T>,
+    positions: vec::IntoIter<usize>,
+}
+
+impl<T: fmt::Debug> fmt::Debug for IterSorted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items: Vec<&T> = self.clone().collect();
+        f.debug_tuple("IterSorted").field(&items).finish()
+    }
+}
+
+impl<T> Clone for IterSorted<'_, T> {
+    fn clone(&self) -> Self {
+        IterSorted {
+            beap: self.beap,
+            positions: self.positions.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterSorted<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.positions.next().map(|i| &self.beap.data[i])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.positions.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterSorted<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.positions.next_back().map(|i| &self.beap.data[i])
+    }
+}
+
+impl<T> ExactSizeIterator for IterSorted<'_, T> {}
+
+impl<T> FusedIterator for IterSorted<'_, T> {}
+
+/// An owning iterator over the elements of a `Beap` in sorted order.
+///
+/// This `struct` is created by [`Beap::into_iter_sorted()`]. See its
+/// documentation for more.
+#[derive(Clone, Debug)]
+pub struct IntoIterSorted<T> {
+    inner: Beap<T>,
+}
+
+impl<T: Ord> Iterator for IntoIterSorted<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.inner.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.inner.len();
+        (len, Some(len))
+    }
+}
+
+impl<T: Ord> DoubleEndedIterator for IntoIterSorted<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.pop_tail()
+    }
+}
+
+impl<T: Ord> ExactSizeIterator for IntoIterSorted<T> {}
+
+impl<T: Ord> FusedIterator for IntoIterSorted<T> {}
+
+/// A draining iterator over the elements of a `Beap` in sorted order.
+///
+/// This `struct` is created by [`Beap::drain_sorted()`]. See its
+/// documentation for more.
+#[derive(Debug)]
+pub struct DrainSorted<'a, T: 'a + Ord> {
+    inner: &'a mut Beap<T>,
+}
+
+impl<T: Ord> Drop for DrainSorted<'_, T> {
+    fn drop(&mut self) {
+        self.inner.clear();
+    }
+}
+
+impl<T: Ord> Iterator for DrainSorted<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.inner.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.inner.len();
+        (len, Some(len))
+    }
+}
+
+impl<T: Ord> DoubleEndedIterator for DrainSorted<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.pop_tail()
+    }
+}
+
+impl<T: Ord> ExactSizeIterator for DrainSorted<'_, T> {}
+
+impl<T: Ord> FusedIterator for DrainSorted<'_, T> {}
+
 /// A draining iterator over the elements of a `Beap`.
 ///
 /// This `struct` is created by [`Beap::drain()`]. See its
@@ -207,7 +584,26 @@ impl<T> FusedIterator for IntoIter<T> {}
 /// [`drain`]: Beap::drain
 #[derive(Debug)]
 pub struct Drain<'a, T: 'a> {
-    iter: std::vec::Drain<'a, T>,
+    iter: vec::Drain<'a, T>,
+}
+
+impl<T> Drain<'_, T> {
+    /// Returns the remaining items of this iterator as a slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from([1, 2, 3]);
+    /// let mut drain = beap.drain();
+    ///
+    /// assert_eq!(drain.as_slice(), &[3, 2, 1]);
+    /// let _ = drain.next().unwrap();
+    /// assert_eq!(drain.as_slice(), &[2, 1]);
+    /// ```
+    pub fn as_slice(&self) -> &[T] {
+        self.iter.as_slice()
+    }
 }
 
 impl<T> Iterator for Drain<'_, T> {
@@ -232,3 +628,72 @@ impl<T> DoubleEndedIterator for Drain<'_, T> {
 }
 
 impl<T> FusedIterator for Drain<'_, T> {}
+
+/// An iterator which uses a closure to determine if an element should be removed.
+///
+/// This `struct` is created by [`Beap::extract_if()`]. See its
+/// documentation for more.
+///
+/// [`extract_if`]: Beap::extract_if
+pub struct ExtractIf<'a, T: 'a + Ord, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    beap: &'a mut Beap<T>,
+    data: Vec<T>,
+    height: usize,
+    pos: usize,
+    pred: F,
+    sift: bool,
+}
+
+impl<T: Ord + fmt::Debug, F> fmt::Debug for ExtractIf<'_, T, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ExtractIf")
+            .field(&&self.data[self.pos..])
+            .finish()
+    }
+}
+
+impl<T: Ord, F> Drop for ExtractIf<'_, T, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    fn drop(&mut self) {
+        self.beap.data = mem::take(&mut self.data);
+        if self.sift {
+            self.beap.rebuild();
+        } else {
+            self.beap.height = self.height;
+        }
+    }
+}
+
+impl<T: Ord, F> Iterator for ExtractIf<'_, T, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while self.pos < self.data.len() {
+            if (self.pred)(&self.data[self.pos]) {
+                // The last element takes the place of the removed one and is checked next.
+                self.sift = true;
+                return Some(self.data.swap_remove(self.pos));
+            }
+            self.pos += 1;
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.data.len() - self.pos))
+    }
+}
+
+impl<T: Ord, F> FusedIterator for ExtractIf<'_, T, F> where F: FnMut(&T) -> bool {}
@@ -19,7 +19,7 @@
 //! # Read about bi-parental heap:
 //! * [Wikipedia](https://en.wikipedia.org/wiki/Beap)
 //!
-//! [`BinaryHeap`]: std::collections::BinaryHeap
+//! [`BinaryHeap`]: alloc::collections::BinaryHeap
 //!
 //! # Operations
 //! | Operation       | Method                  | Time complexity  |
@@ -37,18 +37,94 @@
 //! | into sorted     | `Beap::into_sorted_vec` | *O*(n*log(*n*))  |
 //! | ............... | ....................... | ................ |
 //!
+//! # `no_std`
+//!
+//! The crate is `no_std` and only depends on the `alloc` crate.
+//!
+
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+mod cached;
 mod core;
+mod entry;
+mod indexed;
 pub mod iter;
 mod mem;
+mod min;
+#[cfg(feature = "rand")]
+mod rand;
+#[cfg(feature = "rayon")]
+mod rayon;
 
-pub use iter::{Drain, IntoIter, Iter};
-use std::fmt;
-use std::ops::{Deref, DerefMut};
+pub use crate::core::height_for;
+use ::core::cmp::Ordering;
+use ::core::fmt;
+use ::core::mem::ManuallyDrop;
+use ::core::ops::{Deref, DerefMut, Index};
+use ::core::ptr;
+use alloc::vec::Vec;
+pub use cached::CachedTailBeap;
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
+pub use indexed::{IndexedBeap, Key};
+pub use iter::{Blocks, Drain, DrainSorted, ExtractIf, IntoIter, IntoIterSorted, Iter, IterSorted};
+pub use min::MinBeap;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+/// Creates a [`Beap`] containing the arguments.
+///
+/// `beap!` allows `Beap`s to be defined with the same syntax as array expressions
+/// or [`vec!`](alloc::vec!). The elements are heapified once.
+///
+/// # Examples
+///
+/// - Create a `Beap` containing a given list of elements:
+///
+/// ```
+/// use beap::beap;
+/// let beap = beap![1, 5, 2];
+/// assert_eq!(beap.peek(), Some(&5));
+/// assert_eq!(beap.len(), 3);
+/// ```
+///
+/// - Create a `Beap` from a given element and size:
+///
+/// ```
+/// use beap::beap;
+/// let beap = beap![1; 3];
+/// assert_eq!(beap.into_sorted_vec(), vec![1, 1, 1]);
+/// ```
+///
+/// - Create an empty `Beap`:
+///
+/// ```
+/// use beap::{beap, Beap};
+/// let beap: Beap<i32> = beap![];
+/// assert!(beap.is_empty());
+/// ```
+#[macro_export]
+macro_rules! beap {
+    () => (
+        $crate::Beap::new()
+    );
+    ($elem:expr; $n:expr) => (
+        $crate::Beap::from($crate::__private::vec![$elem; $n])
+    );
+    ($($x:expr),+ $(,)?) => (
+        $crate::Beap::from($crate::__private::vec![$($x),+])
+    );
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec;
+}
+
 /// A priority queue implemented with a bi-parental heap (beap).
 ///
 /// This will be a max-heap.
@@ -118,7 +194,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// ## Min-heap
 ///
-/// Either [`std::cmp::Reverse`] or a custom [`Ord`] implementation can be used to
+/// Either [`Reverse`](::core::cmp::Reverse) or a custom [`Ord`] implementation can be used to
 /// make `Beap` a min-heap. This makes `beap.pop()` return the smallest
 /// value instead of the greatest one.
 ///
@@ -140,6 +216,20 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(beap.pop(), None);
 /// ```
 ///
+/// [`MinBeap`] does the wrapping for you. Use [`Beap::new_min`] to create an empty one,
+/// or [`Beap::into_min`] to turn an existing beap into one.
+///
+/// ```
+/// use beap::Beap;
+///
+/// let mut beap = Beap::new_min();
+/// beap.push(1);
+/// beap.push(5);
+/// beap.push(2);
+///
+/// assert_eq!(beap.pop(), Some(1));
+/// ```
+///
 /// ## Sorting
 ///
 /// ```
@@ -148,14 +238,28 @@ use serde::{Deserialize, Serialize};
 /// let beap = Beap::from([5, 3, 1, 7]);
 /// assert_eq!(beap.into_sorted_vec(), vec![1, 3, 5, 7]);
 /// ```
-
-#[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Beap<T> {
     data: Vec<T>,
     height: usize,
 }
 
+/// Snapshot of the extremes and the length of a `Beap`.
+///
+/// This `struct` is created by the [`summary`] method on [`Beap`]. See
+/// its documentation for more.
+///
+/// [`summary`]: Beap::summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary<'a, T: 'a> {
+    /// The greatest item.
+    pub max: &'a T,
+    /// The smallest item.
+    pub min: &'a T,
+    /// The number of items.
+    pub len: usize,
+}
+
 /// Structure wrapping a mutable reference to the greatest item on a `Beap`.
 ///
 /// This `struct` is created by the [`peek_mut`] method on [`Beap`]. See
@@ -173,6 +277,61 @@ impl<T: Ord + fmt::Debug> fmt::Debug for PeekMut<'_, T> {
     }
 }
 
+impl<T: fmt::Debug> fmt::Debug for Beap<T> {
+    /// The alternate form (`{:#?}`) renders the beap as a triangle, one block per line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(format!("{:?}", beap), "Beap { data: [4, 3, 2, 1], height: 3 }");
+    /// assert_eq!(format!("{:#?}", beap), "Beap {\n    [4],\n    [3, 2],\n    [1],\n}");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("Beap {\n")?;
+            for block in self.blocks() {
+                writeln!(f, "    {:?},", block)?;
+            }
+            f.write_str("}")
+        } else {
+            f.debug_struct("Beap")
+                .field("data", &self.data)
+                .field("height", &self.height)
+                .finish()
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Beap<T> {
+    /// Renders the beap as a triangle, one block per line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([1, 2, 3, 4, 5]);
+    ///
+    /// assert_eq!(beap.to_string(), "5\n4 3\n2 1");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (b, block) in self.blocks().enumerate() {
+            if b > 0 {
+                f.write_str("\n")?;
+            }
+            for (i, x) in block.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(" ")?;
+                }
+                write!(f, "{}", x)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl<T> Default for Beap<T> {
     fn default() -> Self {
         Self::new()
@@ -210,6 +369,51 @@ impl<'a, T: Ord> PeekMut<'a, T> {
         this.sift = false;
         value
     }
+
+    /// Replaces the peeked value with `new` and returns the old one.
+    ///
+    /// The beap property is restored when the guard is dropped at the end of the call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use beap::{Beap, PeekMut};
+    /// let mut beap = Beap::from([1, 5, 3]);
+    ///
+    /// let top = beap.peek_mut().unwrap();
+    /// assert_eq!(PeekMut::replace(top, 0), 5);
+    /// assert_eq!(beap.peek(), Some(&3));
+    /// ```
+    pub fn replace(mut this: PeekMut<'a, T>, new: T) -> T {
+        this.sift = true;
+        ::core::mem::replace(&mut this.beap.data[0], new)
+    }
+
+    /// Converts the guard into a [`PosMut`] at position 0,
+    /// so it can be passed to position-based code.
+    ///
+    /// A pending sift is carried over to the `PosMut`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use beap::{Beap, PeekMut, PosMut};
+    /// let mut beap = Beap::from([1, 5, 3]);
+    ///
+    /// let top = beap.peek_mut().unwrap();
+    /// let pos = PeekMut::into_pos_mut(top);
+    /// assert_eq!(PosMut::remove(pos), 5);
+    /// assert_eq!(beap.peek(), Some(&3));
+    /// ```
+    pub fn into_pos_mut(this: PeekMut<'a, T>) -> PosMut<'a, T> {
+        let this = ManuallyDrop::new(this);
+        PosMut {
+            // SAFETY: `this` is never dropped, so the reference is moved out exactly once.
+            beap: unsafe { ptr::read(&this.beap) },
+            sift: this.sift,
+            pos: 0,
+        }
+    }
 }
 
 impl<T: Clone> Clone for Beap<T> {
@@ -226,6 +430,117 @@ impl<T: Clone> Clone for Beap<T> {
     }
 }
 
+impl<T: Ord> PartialEq for Beap<T> {
+    /// Two beaps are equal if they contain the same elements, as multisets,
+    /// regardless of their layouts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use beap::Beap;
+    ///
+    /// let mut a = Beap::from([1, 2, 2]);
+    /// let b = Beap::from([2, 1, 2]);
+    /// assert_eq!(a, b);
+    ///
+    /// a.push(3);
+    /// assert_ne!(a, b);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)), or *O*(1) if the lengths differ.
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && sorted_desc(self) == sorted_desc(other)
+    }
+}
+
+impl<T: Ord> Eq for Beap<T> {}
+
+impl<T: Ord> PartialOrd for Beap<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> Ord for Beap<T> {
+    /// Compares the elements of two beaps lexicographically, in descending order.
+    ///
+    /// This is consistent with [`PartialEq`]: beaps with the same elements compare equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use beap::Beap;
+    ///
+    /// assert!(Beap::from([3, 1]) > Beap::from([2, 2, 2]));
+    /// assert!(Beap::from([3, 1]) < Beap::from([3, 2]));
+    /// assert!(Beap::from([3, 1]) < Beap::from([1, 3, 0]));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)), the elements of both beaps are sorted for each comparison.
+    fn cmp(&self, other: &Self) -> Ordering {
+        sorted_desc(self).cmp(&sorted_desc(other))
+    }
+}
+
+/// References to the elements of a beap, sorted in descending order.
+fn sorted_desc<T: Ord>(beap: &Beap<T>) -> Vec<&T> {
+    let mut items: Vec<&T> = beap.data.iter().collect();
+    items.sort_unstable_by(|x, y| y.cmp(x));
+    items
+}
+
+impl<T> Index<usize> for Beap<T> {
+    type Output = T;
+
+    /// Returns a reference to the item at the given position of the underlying vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds, use [`get`] for a non-panicking access.
+    ///
+    /// [`get`]: Beap::get
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([1, 5, 2]);
+    ///
+    /// assert_eq!(beap[0], *beap.peek().unwrap());
+    /// assert_eq!(beap[2], 1);
+    /// ```
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        &self.data[index]
+    }
+}
+
+impl<T> AsRef<[T]> for Beap<T> {
+    /// Returns the underlying vector as a slice, in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use beap::Beap;
+    ///
+    /// fn sum(items: impl AsRef<[i32]>) -> i32 {
+    ///     items.as_ref().iter().sum()
+    /// }
+    ///
+    /// let beap = Beap::from([1, 2, 3]);
+    /// assert_eq!(sum(&beap), 6);
+    /// ```
+    fn as_ref(&self) -> &[T] {
+        self.data.as_slice()
+    }
+}
+
 /// Structure wrapping a mutable reference to the smallest item on a `Beap`.
 ///
 /// This `struct` is created by the [`tail_mut`] method on [`Beap`]. See
@@ -275,6 +590,52 @@ impl<'a, T: Ord> TailMut<'a, T> {
         this.sift = false;
         value
     }
+
+    /// Replaces the smallest value with `new` and returns the old one.
+    ///
+    /// The beap property is restored when the guard is dropped at the end of the call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use beap::{Beap, TailMut};
+    /// let mut beap = Beap::from([1, 5, 3]);
+    ///
+    /// let tail = beap.tail_mut().unwrap();
+    /// assert_eq!(TailMut::replace(tail, 2), 1);
+    /// assert_eq!(beap.tail(), Some(&2));
+    /// assert_eq!(beap.peek(), Some(&5));
+    /// ```
+    pub fn replace(mut this: TailMut<'a, T>, new: T) -> T {
+        this.sift = true;
+        ::core::mem::replace(&mut this.beap.data[this.pos], new)
+    }
+
+    /// Converts the guard into a [`PosMut`] at the position of the smallest item,
+    /// so it can be passed to position-based code.
+    ///
+    /// A pending repair is carried over to the `PosMut`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use beap::{Beap, PosMut, TailMut};
+    /// let mut beap = Beap::from([1, 5, 3]);
+    ///
+    /// let tail = beap.tail_mut().unwrap();
+    /// let pos = TailMut::into_pos_mut(tail);
+    /// assert_eq!(PosMut::remove(pos), 1);
+    /// assert_eq!(beap.tail(), Some(&3));
+    /// ```
+    pub fn into_pos_mut(this: TailMut<'a, T>) -> PosMut<'a, T> {
+        let this = ManuallyDrop::new(this);
+        PosMut {
+            // SAFETY: `this` is never dropped, so the reference is moved out exactly once.
+            beap: unsafe { ptr::read(&this.beap) },
+            sift: this.sift,
+            pos: this.pos,
+        }
+    }
 }
 
 /// Structure wrapping a mutable reference to the item with provided index on a `Beap`.
@@ -323,9 +684,78 @@ impl<'a, T: Ord> PosMut<'a, T> {
     /// Removes the borrowed value from the beap and returns it.
     pub fn remove(mut this: PosMut<'a, T>) -> T {
         let value = this.beap.remove_index(this.pos).unwrap();
-        this.sift = true;
+        this.sift = false;
         value
     }
+
+    /// Overwrites the borrowed value with `new`.
+    ///
+    /// Unlike writing through [`DerefMut`], the beap is repaired on drop
+    /// only if `new` is not equal to the old value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use beap::{Beap, PosMut};
+    /// let mut beap = Beap::from([1, 5, 3]);
+    ///
+    /// let mut top = beap.get_mut(0).unwrap();
+    /// PosMut::set(&mut top, 5); // no repair needed
+    /// PosMut::set(&mut top, 0);
+    /// drop(top);
+    /// assert_eq!(beap.peek(), Some(&3));
+    /// ```
+    pub fn set(this: &mut PosMut<'a, T>, new: T) {
+        let old = &mut this.beap.data[this.pos];
+        if *old != new {
+            this.sift = true;
+        }
+        *old = new;
+    }
+}
+
+/// Structure wrapping mutable references to several items with provided indexes on a `Beap`.
+///
+/// This `struct` is created by the [`get_many_mut`] method on [`Beap`]. See
+/// its documentation for more.
+///
+/// [`get_many_mut`]: Beap::get_many_mut
+pub struct ManyPosMut<'a, T: 'a + Ord, const N: usize> {
+    beap: &'a mut Beap<T>,
+    sift: bool,
+    positions: [usize; N],
+}
+
+impl<T: Ord + fmt::Debug, const N: usize> fmt::Debug for ManyPosMut<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ManyPosMut").field(&self.values()).finish()
+    }
+}
+
+impl<T: Ord, const N: usize> Drop for ManyPosMut<'_, T, N> {
+    fn drop(&mut self) {
+        if self.sift {
+            self.beap.repair_many(&self.positions);
+        }
+    }
+}
+
+impl<T: Ord, const N: usize> ManyPosMut<'_, T, N> {
+    /// Returns references to the borrowed values, in the order of the requested positions.
+    pub fn values(&self) -> [&T; N] {
+        self.positions.map(|pos| &self.beap.data[pos])
+    }
+
+    /// Returns mutable references to the borrowed values, in the order of the requested positions.
+    ///
+    /// The beap property is restored when the `ManyPosMut` is dropped.
+    pub fn values_mut(&mut self) -> [&mut T; N] {
+        self.sift = true;
+        self.beap
+            .data
+            .get_disjoint_mut(self.positions)
+            .expect("positions are checked in `get_many_mut`")
+    }
 }
 
 #[cfg(test)]
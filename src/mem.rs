@@ -1,6 +1,10 @@
 //! Memory management.
 use super::Beap;
-use std::collections::TryReserveError;
+use crate::core::height_for;
+use alloc::boxed::Box;
+use alloc::collections::{BinaryHeap, TryReserveError};
+use alloc::vec::Vec;
+use core::alloc::Layout;
 
 impl<T> Beap<T> {
     /// Creates an empty `Beap` as a max-beap.
@@ -20,11 +24,32 @@ impl<T> Beap<T> {
     #[must_use]
     pub fn new() -> Beap<T> {
         Beap {
-            data: vec![],
+            data: Vec::new(),
             height: 0,
         }
     }
 
+    /// Creates an empty `Beap` as a max-beap.
+    ///
+    /// This is the same as [`new`](Beap::new), spelling out that
+    /// the greatest item comes first. See [`new_min`](Beap::new_min) for the opposite.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::new_max();
+    /// beap.push(1);
+    /// beap.push(4);
+    /// assert_eq!(beap.peek(), Some(&4));
+    /// ```
+    #[must_use]
+    pub fn new_max() -> Beap<T> {
+        Beap::new()
+    }
+
     /// Creates an empty `Beap` with a specific capacity.
     /// This preallocates enough memory for `capacity` elements,
     /// so that the `Beap` does not have to be reallocated
@@ -47,6 +72,29 @@ impl<T> Beap<T> {
         }
     }
 
+    /// Creates an empty `Beap` with enough capacity for `n` elements.
+    ///
+    /// This is the same as [`with_capacity`](Beap::with_capacity): the height
+    /// stays 0 until the first push and then grows block by block, it is not preset.
+    /// Use [`height_for`] to find the height the beap will have.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::{height_for, Beap};
+    /// let mut beap = Beap::with_capacity_for(100);
+    /// assert!(beap.capacity() >= 100);
+    ///
+    /// beap.extend(0..100);
+    /// assert_eq!(beap.blocks().count(), height_for(100));
+    /// ```
+    #[must_use]
+    pub fn with_capacity_for(n: usize) -> Beap<T> {
+        Beap::with_capacity(n)
+    }
+
     /// Returns the number of elements the beap can hold without reallocating.
     ///
     /// # Examples
@@ -153,7 +201,8 @@ impl<T> Beap<T> {
     /// Discards capacity with a lower bound.
     ///
     /// The capacity will remain at least as large as both the length
-    /// and the supplied value.
+    /// and the supplied value, so no element is ever dropped:
+    /// `shrink_to(0)` on a non-empty beap behaves like [`shrink_to_fit`](Beap::shrink_to_fit).
     ///
     /// If the current capacity is less than the lower limit, this is a no-op.
     ///
@@ -166,6 +215,10 @@ impl<T> Beap<T> {
     /// assert!(beap.capacity() >= 100);
     /// beap.shrink_to(10);
     /// assert!(beap.capacity() >= 10);
+    ///
+    /// beap.extend([1, 2, 3]);
+    /// beap.shrink_to(0);
+    /// assert!(beap.capacity() >= 3);
     /// ```
     #[inline]
     pub fn shrink_to(&mut self, min_capacity: usize) {
@@ -194,6 +247,54 @@ impl<T> Beap<T> {
         self.data
     }
 
+    /// Decomposes a `Beap` into its underlying vector and height,
+    /// the number of its last block.
+    ///
+    /// The parts can be turned back into a `Beap` with [`from_raw_parts`](Beap::from_raw_parts).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([1, 2, 3, 4]);
+    ///
+    /// let (data, height) = beap.into_raw_parts();
+    /// assert_eq!(data, vec![4, 3, 2, 1]);
+    /// assert_eq!(height, 3);
+    /// ```
+    pub fn into_raw_parts(self) -> (Vec<T>, usize) {
+        (self.data, self.height)
+    }
+
+    /// Creates a `Beap` directly from its underlying vector and height without any checks.
+    ///
+    /// `data` should be a valid beap layout, i.e. every element is not greater than
+    /// its parents, and `height` should be equal to
+    /// [`height_for(data.len())`](crate::height_for), as returned by
+    /// [`into_raw_parts`](Beap::into_raw_parts).
+    ///
+    /// It is a logic error to pass parts that do not satisfy these conditions.
+    /// The behavior resulting from such a logic error is not specified, but will be
+    /// encapsulated to the `Beap` and not result in undefined behavior: its methods
+    /// may return wrong results or panic.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let (data, height) = Beap::from([1, 2, 3, 4]).into_raw_parts();
+    ///
+    /// let beap = Beap::from_raw_parts(data, height);
+    /// assert_eq!(beap.into_sorted_vec(), vec![1, 2, 3, 4]);
+    /// ```
+    pub fn from_raw_parts(data: Vec<T>, height: usize) -> Beap<T> {
+        Beap { data, height }
+    }
+
     /// Returns the length of the beap.
     ///
     /// # Examples
@@ -257,6 +358,53 @@ impl<T> Beap<T> {
         self.drain();
     }
 
+    /// Drops all items from the beap and frees its memory.
+    ///
+    /// Unlike [`clear`](Beap::clear), which keeps the allocated capacity
+    /// for future items, this shrinks the capacity of the beap to zero.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap: Beap<i32> = (0..100).collect();
+    ///
+    /// beap.clear_and_shrink();
+    ///
+    /// assert!(beap.is_empty());
+    /// assert_eq!(beap.capacity(), 0);
+    /// ```
+    pub fn clear_and_shrink(&mut self) {
+        self.clear();
+        self.shrink_to_fit();
+    }
+
+    /// Drops all items from the beap and shrinks its capacity to `cap`.
+    ///
+    /// This keeps a reused beap, e.g. one from an object pool, from retaining
+    /// the memory of its largest use. The capacity is never increased:
+    /// a beap with capacity less than `cap` keeps it.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap: Beap<i32> = (0..100).collect();
+    ///
+    /// beap.reset_to(16);
+    ///
+    /// assert!(beap.is_empty());
+    /// assert!(beap.capacity() >= 16 && beap.capacity() < 100);
+    /// ```
+    pub fn reset_to(&mut self, cap: usize) {
+        self.clear();
+        self.shrink_to(cap);
+    }
+
     /// Consumes and leaks the `Vec`, returning a mutable reference to the contents, `&'a mut [T]`.
     ///
     /// This calls [Vec::leak], accordingly, there are all lifetime restrictions.
@@ -395,6 +543,189 @@ impl<T> Beap<T> {
     pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
         self.data.try_reserve_exact(additional)
     }
+
+    /// Checks whether `additional` more elements could be reserved without
+    /// a capacity overflow.
+    ///
+    /// Returns `false` if `self.len() + additional` overflows, or if the required memory
+    /// exceeds `isize::MAX` bytes. The check is purely arithmetic: the allocator is not
+    /// consulted, so a later reservation may still fail if the memory is exhausted.
+    /// Use [`try_reserve`](Beap::try_reserve) to actually reserve the capacity.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([1, 2, 3]);
+    ///
+    /// assert!(beap.can_reserve(10));
+    /// assert!(!beap.can_reserve(usize::MAX));
+    /// ```
+    #[must_use]
+    pub fn can_reserve(&self, additional: usize) -> bool {
+        match self.len().checked_add(additional) {
+            None => false,
+            Some(required) if required <= self.capacity() => true,
+            Some(required) => Layout::array::<T>(required).is_ok(),
+        }
+    }
+}
+
+impl<T: Clone> Beap<T> {
+    /// Clones the underlying vector into `buf`, reusing its allocation.
+    ///
+    /// `buf` is cleared first and ends up in the beap layout,
+    /// the same as [`as_slice`](Beap::as_slice) returns.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([1, 3, 2]);
+    /// let mut buf = Vec::with_capacity(10);
+    /// buf.push(7);
+    ///
+    /// beap.clone_into_vec(&mut buf);
+    /// assert_eq!(buf, [3, 2, 1]);
+    /// assert!(buf.capacity() >= 10);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*).
+    pub fn clone_into_vec(&self, buf: &mut Vec<T>) {
+        buf.clear();
+        buf.extend_from_slice(&self.data);
+    }
+}
+
+impl<T: Ord> Beap<T> {
+    /// Creates a `Beap` from a vector sorted in descending order,
+    /// which is already a valid beap layout, so no sorting is done.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the vector is not sorted in descending order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::from_sorted_vec(vec![5, 4, 3, 2, 1]);
+    /// assert_eq!(beap.peek(), Some(&5));
+    /// assert_eq!(beap.tail(), Some(&1));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(1) in release builds and *O*(*n*) in debug builds.
+    pub fn from_sorted_vec(vec: Vec<T>) -> Beap<T> {
+        debug_assert!(
+            vec.windows(2).all(|w| w[0] >= w[1]),
+            "the vector is not sorted in descending order"
+        );
+        Beap {
+            height: height_for(vec.len()),
+            data: vec,
+        }
+    }
+
+    /// Creates a `Beap` from an iterator yielding items in descending order,
+    /// which are collected as is, so no sorting is done.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the items are not in descending order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from_sorted_desc_iter((1..=5).rev());
+    /// assert_eq!(beap.peek(), Some(&5));
+    /// assert_eq!(beap.tail(), Some(&1));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*) to collect the items.
+    pub fn from_sorted_desc_iter<I: IntoIterator<Item = T>>(iter: I) -> Beap<T> {
+        Beap::from_sorted_vec(iter.into_iter().collect())
+    }
+
+    /// Adopts a vector which is already a valid beap layout, without sorting it.
+    ///
+    /// Returns the vector back as an error if it is not a valid beap layout.
+    ///
+    /// This is not a `TryFrom<Vec<T>>` implementation, because `From<Vec<T>>`
+    /// already provides an infallible one that heapifies the vector.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    ///
+    /// let beap = Beap::try_from_vec(vec![9, 8, 7, 5, 6, 1]).unwrap();
+    /// assert_eq!(beap.peek(), Some(&9));
+    ///
+    /// assert_eq!(Beap::try_from_vec(vec![1, 2, 3]).unwrap_err(), vec![1, 2, 3]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*).
+    pub fn try_from_vec(vec: Vec<T>) -> Result<Beap<T>, Vec<T>> {
+        let beap = Beap {
+            height: height_for(vec.len()),
+            data: vec,
+        };
+        if beap.is_valid() {
+            Ok(beap)
+        } else {
+            Err(beap.data)
+        }
+    }
+
+    /// Creates a `Beap` from a vector sorted in descending order without any checks,
+    /// even in debug builds.
+    ///
+    /// The vector should be sorted in descending order. The height is always computed
+    /// from the length, so only the order of the elements is trusted.
+    ///
+    /// It is a logic error to pass a vector that is not sorted in descending order.
+    /// The behavior resulting from such a logic error is not specified, but will be
+    /// encapsulated to the `Beap` and not result in undefined behavior: its methods
+    /// may return wrong results or panic.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from_sorted_vec_unchecked(vec![3, 2, 1]);
+    /// assert_eq!(beap.peek(), Some(&3));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(1).
+    pub fn from_sorted_vec_unchecked(vec: Vec<T>) -> Beap<T> {
+        Beap {
+            height: height_for(vec.len()),
+            data: vec,
+        }
+    }
 }
 
 impl<T: Ord> From<Vec<T>> for Beap<T> {
@@ -411,19 +742,45 @@ impl<T: Ord> From<Vec<T>> for Beap<T> {
     /// let beap = Beap::from(vec![5, 3, 2, 4, 1]);
     /// assert_eq!(beap.into_sorted_vec(), vec![1, 2, 3, 4, 5]);
     /// ```
-    fn from(mut vec: Vec<T>) -> Beap<T> {
-        vec.sort_unstable_by(|x, y| y.cmp(x));
-        let h = ((vec.len() * 2) as f64).sqrt().round() as usize;
-        Beap {
+    fn from(vec: Vec<T>) -> Beap<T> {
+        let mut beap = Beap {
             data: vec,
-            height: h,
-        }
+            height: 0,
+        };
+        beap.rebuild();
+        beap
+    }
+}
+
+impl<T: Ord> From<Beap<T>> for BinaryHeap<T> {
+    /// Converts a `Beap<T>` into a `BinaryHeap<T>`.
+    ///
+    /// The underlying vector is handed to `BinaryHeap::from`,
+    /// so the conversion has *O*(*n*) time complexity.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let beap = Beap::from([1, 4, 2, 3]);
+    /// let heap = BinaryHeap::from(beap);
+    /// assert_eq!(heap.into_sorted_vec(), vec![1, 2, 3, 4]);
+    /// ```
+    fn from(beap: Beap<T>) -> BinaryHeap<T> {
+        BinaryHeap::from(beap.data)
     }
 }
 
 impl<T: Ord, const N: usize> From<[T; N]> for Beap<T> {
     /// Converts a `[T, N]` into a `Beap<T>`.
     ///
+    /// The array is moved into a vector and converted with [`From<Vec<T>>`],
+    /// so both conversions build the beap the same way and at the same cost.
+    ///
     /// This conversion has *O*(*nlog(n)*) time complexity.
     ///
     /// # Examples
@@ -462,10 +819,33 @@ impl<T: Ord> FromIterator<T> for Beap<T> {
     /// }
     /// ```
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Beap<T> {
+        // Collecting into a `Vec` already reserves using the `size_hint` of the iterator,
+        // and the beap is built with a single sort afterwards.
         Beap::from(iter.into_iter().collect::<Vec<_>>())
     }
 }
 
+impl<'a, T: 'a + Ord + Copy> FromIterator<&'a T> for Beap<T> {
+    /// Building Beap from an iterator of references, copying the items.
+    ///
+    /// This conversion has *O*(*nlog(n)*) time complexity.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    ///
+    /// let items = [1, 2, 3];
+    /// let beap: Beap<i32> = items.iter().collect();
+    /// assert_eq!(beap.into_sorted_vec(), [1, 2, 3]);
+    /// ```
+    fn from_iter<I: IntoIterator<Item = &'a T>>(iter: I) -> Beap<T> {
+        iter.into_iter().copied().collect()
+    }
+}
+
 impl<T: Ord> Extend<T> for Beap<T> {
     /// Extend Beap with elements from the iterator.
     ///
@@ -481,6 +861,8 @@ impl<T: Ord> Extend<T> for Beap<T> {
     /// assert_eq!(beap.into_sorted_vec(), [0, 1, 3, 4, 5, 7]);
     /// ```
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
         for x in iter {
             self.push(x);
         }
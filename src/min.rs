@@ -0,0 +1,259 @@
+//! Min-beap wrapper.
+use super::Beap;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+
+/// A priority queue implemented with a bi-parental heap, which will be a min-heap.
+///
+/// `MinBeap<T>` is a thin wrapper around `Beap<Reverse<T>>`
+/// that takes and returns bare `T` values.
+///
+/// # Examples
+///
+/// ```
+/// use beap::MinBeap;
+///
+/// let mut beap = MinBeap::new();
+/// beap.push(5);
+/// beap.push(1);
+/// beap.push(3);
+///
+/// assert_eq!(beap.peek(), Some(&1));
+/// assert_eq!(beap.pop(), Some(1));
+/// assert_eq!(beap.pop(), Some(3));
+/// assert_eq!(beap.pop(), Some(5));
+/// assert_eq!(beap.pop(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MinBeap<T> {
+    beap: Beap<Reverse<T>>,
+}
+
+impl<T> Default for MinBeap<T> {
+    fn default() -> Self {
+        MinBeap::new()
+    }
+}
+
+impl<T> MinBeap<T> {
+    /// Creates an empty `MinBeap`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::MinBeap;
+    /// let mut beap = MinBeap::new();
+    /// beap.push(4);
+    /// assert_eq!(beap.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn new() -> MinBeap<T> {
+        MinBeap { beap: Beap::new() }
+    }
+
+    /// Creates an empty `MinBeap` with a specific capacity.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::MinBeap;
+    /// let mut beap = MinBeap::with_capacity(10);
+    /// beap.push(4);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> MinBeap<T> {
+        MinBeap {
+            beap: Beap::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the smallest item in the beap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::MinBeap;
+    /// let mut beap = MinBeap::new();
+    /// assert_eq!(beap.peek(), None);
+    ///
+    /// beap.push(5);
+    /// beap.push(2);
+    /// assert_eq!(beap.peek(), Some(&2));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(1).
+    pub fn peek(&self) -> Option<&T> {
+        self.beap.peek().map(|Reverse(x)| x)
+    }
+
+    /// Returns the length of the beap.
+    pub fn len(&self) -> usize {
+        self.beap.len()
+    }
+
+    /// Checks if the beap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.beap.is_empty()
+    }
+
+    /// Drops all items from the beap.
+    pub fn clear(&mut self) {
+        self.beap.clear();
+    }
+
+    /// Consumes the `MinBeap` and returns the underlying `Beap<Reverse<T>>`.
+    pub fn into_inner(self) -> Beap<Reverse<T>> {
+        self.beap
+    }
+}
+
+impl<T: Ord> MinBeap<T> {
+    /// Pushes an item onto the beap.
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn push(&mut self, item: T) {
+        self.beap.push(Reverse(item));
+    }
+
+    /// Removes the smallest item from the beap and returns it, or `None` if it is empty.
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn pop(&mut self) -> Option<T> {
+        self.beap.pop().map(|Reverse(x)| x)
+    }
+
+    /// Returns the greatest item in the beap, or `None` if it is empty.
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn tail(&self) -> Option<&T> {
+        self.beap.tail().map(|Reverse(x)| x)
+    }
+
+    /// Returns true if the beap contains a value.
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn contains(&self, val: &T) -> bool {
+        self.beap.contains(reversed(val))
+    }
+
+    /// Removes a value from the beap. Returns whether the value was present in the beap.
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(sqrt(*2n*)).
+    pub fn remove(&mut self, val: &T) -> bool {
+        self.beap.remove(reversed(val))
+    }
+
+    /// Consumes the `MinBeap` and returns a vector in sorted (ascending) order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::MinBeap;
+    ///
+    /// let beap: MinBeap<_> = [4, 1, 3, 2].into_iter().collect();
+    /// assert_eq!(beap.into_sorted_vec(), vec![1, 2, 3, 4]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)).
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut vec: Vec<T> = self.beap.into_vec().into_iter().map(|x| x.0).collect();
+        vec.sort_unstable();
+        vec
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for MinBeap<T> {
+    fn from(vec: Vec<T>) -> MinBeap<T> {
+        MinBeap {
+            beap: vec.into_iter().map(Reverse).collect(),
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MinBeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> MinBeap<T> {
+        MinBeap {
+            beap: iter.into_iter().map(Reverse).collect(),
+        }
+    }
+}
+
+impl<T: Ord> Extend<T> for MinBeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.beap.extend(iter.into_iter().map(Reverse));
+    }
+}
+
+impl<T> Beap<T> {
+    /// Creates an empty [`MinBeap`], which pops the smallest item first.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let mut beap = Beap::new_min();
+    /// beap.push(4);
+    /// beap.push(1);
+    /// assert_eq!(beap.peek(), Some(&1));
+    /// ```
+    #[must_use]
+    pub fn new_min() -> MinBeap<T> {
+        MinBeap::new()
+    }
+}
+
+impl<T: Ord> Beap<T> {
+    /// Consumes the `Beap` and returns a [`MinBeap`] with the same items,
+    /// wrapping each of them in `Reverse`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([3, 1, 4, 2]);
+    /// assert_eq!(beap.peek(), Some(&4));
+    ///
+    /// let mut min = beap.into_min();
+    /// assert_eq!(min.pop(), Some(1));
+    /// assert_eq!(min.pop(), Some(2));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)).
+    pub fn into_min(self) -> MinBeap<T> {
+        MinBeap::from(self.into_vec())
+    }
+}
+
+/// Views a reference to a value as a reference to its `Reverse`.
+fn reversed<T>(val: &T) -> &Reverse<T> {
+    // SAFETY: `Reverse<T>` is `#[repr(transparent)]` over `T`.
+    unsafe { &*(val as *const T as *const Reverse<T>) }
+}
@@ -0,0 +1,34 @@
+//! Random sampling with `rand`.
+use super::Beap;
+use rand::Rng;
+
+impl<T> Beap<T> {
+    /// Returns a uniformly random item of the beap, or `None` if it is empty.
+    ///
+    /// Every item is equally likely to be chosen, regardless of its priority.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// let beap = Beap::from([1, 2, 3]);
+    /// let mut rng = rand::thread_rng();
+    ///
+    /// let x = beap.sample(&mut rng).unwrap();
+    /// assert!(beap.contains(x));
+    /// assert_eq!(Beap::<i32>::new().sample(&mut rng), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(1).
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<&T> {
+        if self.data.is_empty() {
+            None
+        } else {
+            Some(&self.data[rng.gen_range(0..self.data.len())])
+        }
+    }
+}
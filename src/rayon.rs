@@ -0,0 +1,157 @@
+//! Parallel iteration with `rayon`.
+use super::Beap;
+use crate::core::height_for;
+use alloc::vec::Vec;
+use rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelExtend};
+use rayon::slice::ParallelSliceMut;
+
+impl<T: Ord + Send> Beap<T> {
+    /// Converts a `Vec<T>` into a `Beap<T>` like [`From<Vec<T>>`],
+    /// sorting the vector in parallel.
+    ///
+    /// A vector sorted in descending order is a valid beap layout, so the whole
+    /// work is a parallel sort. It pays off for large vectors only.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    ///
+    /// let beap = Beap::par_from_vec((0..10_000).collect());
+    /// assert_eq!(beap.peek(), Some(&9_999));
+    /// assert_eq!(beap.tail(), Some(&0));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)) work, split across the threads of the rayon pool.
+    pub fn par_from_vec(mut vec: Vec<T>) -> Beap<T> {
+        vec.par_sort_unstable_by(|x, y| y.cmp(x));
+        Beap {
+            height: height_for(vec.len()),
+            data: vec,
+        }
+    }
+}
+
+impl<T: Send> IntoParallelIterator for Beap<T> {
+    type Item = T;
+    type Iter = rayon::vec::IntoIter<T>;
+
+    /// Creates a parallel consuming iterator visiting all values
+    /// of the beap in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// use rayon::prelude::*;
+    ///
+    /// let beap = Beap::from([1, 2, 3, 4]);
+    /// let sum: i32 = beap.into_par_iter().sum();
+    /// assert_eq!(sum, 10);
+    /// ```
+    fn into_par_iter(self) -> Self::Iter {
+        self.data.into_par_iter()
+    }
+}
+
+impl<'a, T: Sync> IntoParallelIterator for &'a Beap<T> {
+    type Item = &'a T;
+    type Iter = rayon::slice::Iter<'a, T>;
+
+    /// Creates a parallel iterator visiting all values
+    /// of the beap in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// use rayon::prelude::*;
+    ///
+    /// let beap = Beap::from([1, 2, 3, 4]);
+    /// let max = (&beap).into_par_iter().max();
+    /// assert_eq!(max, beap.peek());
+    /// ```
+    fn into_par_iter(self) -> Self::Iter {
+        self.data.as_slice().into_par_iter()
+    }
+}
+
+impl<T: Ord + Send> FromParallelIterator<T> for Beap<T> {
+    /// Creates a beap from a parallel iterator.
+    ///
+    /// The items are collected into a vector, which is then heapified once
+    /// with [`par_from_vec`](Beap::par_from_vec).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// use rayon::prelude::*;
+    ///
+    /// let beap: Beap<i32> = (0..100).into_par_iter().collect();
+    /// assert_eq!(beap.len(), 100);
+    /// assert_eq!(beap.peek(), Some(&99));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)).
+    fn from_par_iter<I>(par_iter: I) -> Beap<T>
+    where
+        I: IntoParallelIterator<Item = T>,
+    {
+        Beap::par_from_vec(Vec::from_par_iter(par_iter))
+    }
+}
+
+impl<T: Ord + Send> ParallelExtend<T> for Beap<T> {
+    /// Extends the beap with items of a parallel iterator.
+    ///
+    /// Unlike [`Extend`], the items are collected into the underlying vector
+    /// first and the beap is rebuilt only once.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use beap::Beap;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut beap = Beap::from([1, 2, 3]);
+    /// beap.par_extend((4..=100).into_par_iter());
+    /// assert_eq!(beap.len(), 100);
+    /// assert_eq!(beap.peek(), Some(&100));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(*n*log(*n*)), where *n* = self.len() + the number of new items.
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = T>,
+    {
+        self.data.par_extend(par_iter);
+        self.rebuild();
+    }
+}
+
+impl<'a, T: 'a + Ord + Copy + Send + Sync> ParallelExtend<&'a T> for Beap<T> {
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = &'a T>,
+    {
+        self.data.par_extend(par_iter);
+        self.rebuild();
+    }
+}
@@ -1,8 +1,48 @@
-use crate::{Beap, PeekMut, PosMut, TailMut};
+use crate::{Beap, CachedTailBeap, Entry, IndexedBeap, MinBeap, PeekMut, PosMut, TailMut};
 use rand::{thread_rng, Rng};
-use std::cmp::Reverse;
+use std::cell::Cell;
+use std::cmp::{Ordering, Reverse};
 use std::collections::binary_heap;
 use std::collections::{BinaryHeap, HashSet};
+use std::fmt::Debug;
+
+/// Pops every item of `beap` and checks that they come out
+/// in the descending order of `expected`.
+fn assert_pops_sorted<T: Ord + Debug>(mut beap: Beap<T>, mut expected: Vec<T>) {
+    expected.sort_unstable();
+    while let Some(x) = beap.pop() {
+        assert_eq!(Some(x), expected.pop());
+    }
+    assert!(expected.is_empty(), "missing items: {:?}", expected);
+}
+
+thread_local! {
+    static COMPARISONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Item that counts how many times it is compared on the current thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Counted(i32);
+
+impl Counted {
+    /// Returns the number of comparisons made since the last call and resets it.
+    fn take_comparisons() -> usize {
+        COMPARISONS.with(|c| c.replace(0))
+    }
+}
+
+impl PartialOrd for Counted {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Counted {
+    fn cmp(&self, other: &Self) -> Ordering {
+        COMPARISONS.with(|c| c.set(c.get() + 1));
+        self.0.cmp(&other.0)
+    }
+}
 
 #[test]
 fn test_push() {
@@ -363,6 +403,21 @@ fn test_shrink_to() {
     assert_eq!(beap.capacity(), 10);
 }
 
+#[test]
+fn test_shrink_to_below_len() {
+    let mut beap = Beap::with_capacity(20);
+    for x in [3, 1, 5, 2, 4] {
+        beap.push(x);
+    }
+    let layout = beap.as_slice().to_vec();
+
+    beap.shrink_to(0);
+    assert!(beap.capacity() >= 5);
+    assert_eq!(beap.as_slice(), layout.as_slice());
+    assert!(beap.is_valid());
+    assert_eq!(beap.into_sorted_vec(), vec![1, 2, 3, 4, 5]);
+}
+
 #[test]
 fn test_shrink_to_fit() {
     let mut beap: Beap<i32> = Beap::with_capacity(10);
@@ -870,6 +925,14 @@ fn test_append() {
     let mut b2: Beap<i64> = Beap::new();
     b1.append(&mut b2);
     assert_eq!(b1.into_sorted_vec(), vec![]);
+
+    let mut b1 = Beap::with_capacity(16);
+    b1.extend([5, 1, 3]);
+    let mut b2 = Beap::from([4, 2]);
+    b1.append(&mut b2);
+    assert_eq!(b1.capacity(), 16);
+    assert!(b2.is_empty());
+    assert_eq!(b1.into_sorted_vec(), vec![1, 2, 3, 4, 5]);
 }
 
 #[test]
@@ -919,6 +982,12 @@ fn append_vec() {
 
     beap.append_vec(&mut vec![3, 8, 5]);
     assert_eq!(beap.into_sorted_vec(), vec![3, 5, 8]);
+
+    let mut beap = Beap::with_capacity(10);
+    beap.push(4);
+    beap.append_vec(&mut vec![3, 8, 5]);
+    assert_eq!(beap.capacity(), 10);
+    assert_eq!(beap.into_sorted_vec(), vec![3, 4, 5, 8]);
 }
 
 #[test]
@@ -983,8 +1052,8 @@ fn test_extend_random() {
         beap.extend(elements.clone());
         bin_heap.extend(elements.clone());
 
-        beap.extend(elements.clone().into_iter());
-        bin_heap.extend(elements.into_iter());
+        beap.extend(elements.iter().copied());
+        bin_heap.extend(elements.iter().copied());
 
         assert_eq!(beap.len(), bin_heap.len());
         assert_eq!(beap.peek(), bin_heap.peek());
@@ -1134,6 +1203,33 @@ fn test_try_reserve_exact() {
     assert!(b.capacity() >= 53);
 }
 
+#[test]
+fn test_can_reserve() {
+    let mut b = Beap::from([1, 2, 3]);
+    assert!(b.can_reserve(0));
+    assert!(b.can_reserve(50));
+    assert_eq!(b.capacity(), 3);
+
+    // `len + additional` overflows.
+    assert!(!b.can_reserve(usize::MAX));
+    assert!(!b.can_reserve(usize::MAX - 2));
+    // The size in bytes exceeds `isize::MAX`.
+    assert!(!b.can_reserve(usize::MAX / 2));
+    // The allocator is not consulted, so a huge but valid size is accepted.
+    assert!(b.can_reserve(isize::MAX as usize / 4 - 3));
+    assert!(!b.can_reserve(isize::MAX as usize / 4 - 2));
+
+    b.clear();
+    assert!(b.can_reserve(b.capacity()));
+    assert!(!b.can_reserve(usize::MAX));
+
+    // Zero-sized items never need an allocation.
+    let mut b = Beap::new();
+    b.push(());
+    assert!(b.can_reserve(usize::MAX - 1));
+    assert!(!b.can_reserve(usize::MAX));
+}
+
 #[test]
 fn test_index() {
     let mut b = Beap::<i32>::new();
@@ -1160,6 +1256,18 @@ fn test_remove_from_pos() {
     assert_eq!(b.remove_index(0), Some(9));
 }
 
+#[test]
+fn test_remove_index_past_end() {
+    let mut b = Beap::from([1, 2, 3, 4, 5]);
+    assert_eq!(b.remove_index(5), None);
+    assert_eq!(b.remove_index(6), None);
+    assert_eq!(b.len(), 5);
+    assert_eq!(b.into_sorted_vec(), vec![1, 2, 3, 4, 5]);
+
+    let mut b: Beap<i32> = Beap::new();
+    assert_eq!(b.remove_index(0), None);
+}
+
 #[test]
 fn test_get_mut() {
     let mut beap: Beap<i32> = Beap::new();
@@ -1196,3 +1304,3100 @@ fn test_get_mut() {
     }
     assert_eq!(beap.tail(), Some(&4));
 }
+
+#[test]
+fn test_pos_mut_set() {
+    let mut beap = Beap::from([9, 2, 7, 4, 4, 1, 8]);
+    let layout = beap.as_slice().to_vec();
+
+    for pos in 0..beap.len() {
+        let mut x = beap.get_mut(pos).unwrap();
+        let same = *x;
+        PosMut::set(&mut x, same);
+        assert!(!x.sift);
+    }
+    assert_eq!(beap.as_slice(), layout.as_slice());
+
+    {
+        let mut x = beap.get_mut(beap.len() - 1).unwrap();
+        PosMut::set(&mut x, 10);
+        assert!(x.sift);
+        // Writing back an equal value keeps the pending repair.
+        PosMut::set(&mut x, 10);
+        assert!(x.sift);
+    }
+    assert_eq!(beap.peek(), Some(&10));
+    assert!(beap.is_valid());
+}
+
+#[test]
+fn test_nsmallest() {
+    let beap: Beap<i32> = Beap::new();
+    assert_eq!(beap.nsmallest(0), vec![]);
+    assert_eq!(beap.nsmallest(3), vec![]);
+
+    let beap = Beap::from([7, 3, 9, 1, 5, 3]);
+    assert_eq!(beap.nsmallest(0), vec![]);
+    assert_eq!(beap.nsmallest(1), vec![1]);
+    assert_eq!(beap.nsmallest(3), vec![1, 3, 3]);
+    assert_eq!(beap.nsmallest(6), vec![1, 3, 3, 5, 7, 9]);
+    assert_eq!(beap.nsmallest(100), vec![1, 3, 3, 5, 7, 9]);
+    assert_eq!(beap.len(), 6);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_nsmallest_random() {
+    // Random tests against BinaryHeap
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut beap = Beap::new();
+        for _ in 0..size {
+            beap.push(rng.gen_range(-30..=30));
+        }
+
+        for n in [0, 1, size / 2, size, size + 1] {
+            let mut bin_heap: BinaryHeap<Reverse<i64>> = beap.iter().map(|&x| Reverse(x)).collect();
+            let expected: Vec<i64> = (0..n).map_while(|_| bin_heap.pop().map(|x| x.0)).collect();
+            assert_eq!(beap.nsmallest(n), expected);
+        }
+    }
+}
+
+#[test]
+fn test_kth_largest() {
+    let beap: Beap<i32> = Beap::new();
+    assert_eq!(beap.kth_largest(0), None);
+    assert_eq!(beap.kth_largest(1), None);
+
+    let beap = Beap::from([4, 8, 2, 8, 6]);
+    assert_eq!(beap.kth_largest(0), None);
+    assert_eq!(beap.kth_largest(1), Some(&8));
+    assert_eq!(beap.kth_largest(2), Some(&8));
+    assert_eq!(beap.kth_largest(3), Some(&6));
+    assert_eq!(beap.kth_largest(5), Some(&2));
+    assert_eq!(beap.kth_largest(6), None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_kth_largest_random() {
+    // Random tests against a sorted vector
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut beap = Beap::new();
+        for _ in 0..size {
+            beap.push(rng.gen_range(-30..=30));
+        }
+        for _ in 0..size / 3 {
+            beap.pop_tail();
+        }
+
+        let len = beap.len();
+        let sorted = beap.clone().into_sorted_vec();
+        for k in 1..=len {
+            assert_eq!(beap.kth_largest(k), Some(&sorted[len - k]));
+        }
+        assert_eq!(beap.kth_largest(0), None);
+        assert_eq!(beap.kth_largest(len + 1), None);
+    }
+}
+
+#[test]
+fn test_kth_smallest() {
+    let beap: Beap<i32> = Beap::new();
+    assert_eq!(beap.kth_smallest(0), None);
+    assert_eq!(beap.kth_smallest(1), None);
+
+    let beap = Beap::from([4, 8, 2, 8, 6]);
+    assert_eq!(beap.kth_smallest(0), None);
+    assert_eq!(beap.kth_smallest(1), Some(&2));
+    assert_eq!(beap.kth_smallest(3), Some(&6));
+    assert_eq!(beap.kth_smallest(4), Some(&8));
+    assert_eq!(beap.kth_smallest(5), Some(&8));
+    assert_eq!(beap.kth_smallest(6), None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_kth_smallest_random() {
+    // Random tests against a sorted vector
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut beap = Beap::new();
+        for _ in 0..size {
+            beap.push(rng.gen_range(-30..=30));
+        }
+
+        let len = beap.len();
+        let sorted = beap.clone().into_sorted_vec();
+        for k in 1..=len {
+            assert_eq!(beap.kth_smallest(k), Some(&sorted[k - 1]));
+        }
+        assert_eq!(beap.kth_smallest(0), None);
+        assert_eq!(beap.kth_smallest(len), sorted.last());
+        assert_eq!(beap.kth_smallest(len + 1), None);
+    }
+}
+
+#[test]
+fn test_median() {
+    let mut beap: Beap<i32> = Beap::new();
+    assert_eq!(beap.median(), None);
+
+    beap.push(7);
+    assert_eq!(beap.median(), Some(&7));
+
+    beap.push(3);
+    assert_eq!(beap.median(), Some(&3));
+
+    beap.push(5);
+    assert_eq!(beap.median(), Some(&5));
+
+    beap.push(9);
+    assert_eq!(beap.median(), Some(&5));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_median_random() {
+    // Random tests against a sorted vector
+    let mut rng = thread_rng();
+
+    for size in 1..=100 {
+        let mut beap = Beap::new();
+        for _ in 0..size {
+            beap.push(rng.gen_range(-30..=30));
+        }
+
+        let sorted = beap.clone().into_sorted_vec();
+        assert_eq!(beap.median(), Some(&sorted[(size - 1) / 2]));
+    }
+}
+
+#[test]
+fn test_index_operator() {
+    let beap = Beap::from([1, 3, 2, 4]);
+    assert_eq!(beap[0], 4);
+    for i in 0..beap.len() {
+        assert_eq!(Some(&beap[i]), beap.get(i));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_index_operator_out_of_bounds() {
+    let beap = Beap::from([1, 3, 2, 4]);
+    let _ = beap[4];
+}
+
+#[test]
+fn test_display() {
+    let beap: Beap<i32> = Beap::new();
+    assert_eq!(beap.to_string(), "");
+
+    let beap = Beap::from([7]);
+    assert_eq!(beap.to_string(), "7");
+
+    let beap = Beap::from([9, 8, 7, 6, 5, 4, 3, 2, 1]);
+    assert_eq!(beap.to_string(), "9\n8 7\n6 5 4\n3 2 1");
+
+    let beap = Beap::from([1, 2, 3, 4, 5, 6]);
+    assert_eq!(beap.to_string(), "6\n5 4\n3 2 1");
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_into_binary_heap_random() {
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut beap = Beap::new();
+        for _ in 0..size {
+            beap.push(rng.gen_range(-30..=30));
+        }
+
+        let bin_heap = BinaryHeap::from(beap.clone());
+        assert_eq!(bin_heap.len(), beap.len());
+        assert_eq!(bin_heap.peek(), beap.peek());
+
+        let round_trip = Beap::from(bin_heap.into_vec());
+        assert_eq!(round_trip.peek(), beap.peek());
+        assert_eq!(round_trip.into_sorted_vec(), beap.into_sorted_vec());
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_iter() {
+    use rayon::prelude::*;
+
+    let beap: Beap<i64> = (1..=1000).collect();
+    let sum: i64 = beap.iter().sum();
+    assert_eq!((&beap).into_par_iter().sum::<i64>(), sum);
+    assert_eq!(beap.clone().into_par_iter().sum::<i64>(), sum);
+    assert_eq!((&beap).into_par_iter().min(), beap.tail());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_par_from_vec_random() {
+    // Random tests against From<Vec>
+    let mut rng = thread_rng();
+
+    for size in (0..100).chain([10_000, 100_000]) {
+        let v: Vec<i64> = (0..size).map(|_| rng.gen_range(-100..=100)).collect();
+        let mut beap1 = Beap::from(v.clone());
+        let mut beap2 = Beap::par_from_vec(v);
+        assert!(beap2.is_valid());
+        assert_eq!(beap1.height, beap2.height);
+        assert_eq!(beap1.tail(), beap2.tail());
+
+        while let Some(x) = beap1.pop() {
+            assert_eq!(beap2.pop(), Some(x));
+        }
+        assert!(beap2.is_empty());
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_from_par_iter_random() {
+    // Random tests against FromIterator
+    use rayon::prelude::*;
+
+    let mut rng = thread_rng();
+
+    for size in 0..100 {
+        let v: Vec<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        let mut beap1: Beap<i64> = v.iter().copied().collect();
+        let mut beap2: Beap<i64> = v.into_par_iter().collect();
+        assert!(beap2.is_valid());
+        assert_eq!(beap1.len(), beap2.len());
+
+        while let Some(x) = beap1.pop() {
+            assert_eq!(beap2.pop(), Some(x));
+        }
+        assert!(beap2.is_empty());
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_par_extend_random() {
+    // Random tests against Extend
+    use rayon::prelude::*;
+
+    let mut rng = thread_rng();
+
+    let mut beap1 = Beap::new();
+    let mut beap2 = Beap::new();
+    for size in 0..100 {
+        let mut elements: Vec<i64> = Vec::with_capacity(size);
+        for _ in 0..size {
+            elements.push(rng.gen_range(-30..=30));
+        }
+
+        beap1.extend(elements.iter());
+        beap2.par_extend(elements.par_iter());
+        assert_eq!(beap1.len(), beap2.len());
+        assert_eq!(beap1.peek(), beap2.peek());
+
+        beap1.extend(elements.clone());
+        beap2.par_extend(elements);
+        assert_eq!(beap1.tail(), beap2.tail());
+
+        beap1.pop();
+        beap2.pop();
+        assert_eq!(
+            beap1.clone().into_sorted_vec(),
+            beap2.clone().into_sorted_vec()
+        );
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_arbitrary_random() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let mut rng = thread_rng();
+
+    for size in 0..=200 {
+        let bytes: Vec<u8> = (0..size).map(|_| rng.gen()).collect();
+        let mut u = Unstructured::new(&bytes);
+        let mut beap = Beap::<i16>::arbitrary(&mut u).unwrap();
+
+        let mut sorted = beap.clone().into_sorted_vec();
+        assert_eq!(beap.tail(), sorted.first());
+        while let Some(x) = beap.pop() {
+            assert_eq!(Some(x), sorted.pop());
+            assert_eq!(beap.peek(), sorted.last());
+        }
+        assert!(sorted.is_empty());
+    }
+}
+
+#[test]
+fn test_for_each_mut() {
+    let mut beap: Beap<i32> = Beap::new();
+    beap.for_each_mut(|_| unreachable!());
+    assert!(beap.is_empty());
+
+    let mut beap = Beap::from([1, 2, 3, 4, 5]);
+    beap.for_each_mut(|x| *x = -*x);
+    assert!(beap.is_valid());
+    assert_eq!(beap.peek(), Some(&-1));
+    assert_eq!(beap.tail(), Some(&-5));
+
+    beap.for_each_mut(|x| {
+        if *x == -5 {
+            *x = 10;
+        }
+    });
+    assert_eq!(beap.peek(), Some(&10));
+    assert_eq!(beap.into_sorted_vec(), vec![-4, -3, -2, -1, 10]);
+}
+
+#[test]
+fn test_for_each_mut_panic() {
+    let mut beap = Beap::from([1, 2, 3, 4, 5]);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        beap.for_each_mut(|x| {
+            if *x == 3 {
+                panic!("stop");
+            }
+            *x = -*x;
+        })
+    }));
+    assert!(result.is_err());
+    assert!(beap.is_valid());
+    assert_eq!(beap.into_sorted_vec(), vec![-5, -4, 1, 2, 3]);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_for_each_mut_random() {
+    // Random tests against pop order
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut beap = Beap::new();
+        for _ in 0..size {
+            beap.push(rng.gen_range(-30..=30));
+        }
+
+        let mut expected: Vec<i64> = beap.iter().map(|&x| -x).collect();
+        expected.sort_unstable_by(|x, y| y.cmp(x));
+
+        beap.for_each_mut(|x| *x = -*x);
+
+        for x in expected {
+            assert_eq!(beap.pop(), Some(x));
+        }
+        assert!(beap.is_empty());
+    }
+}
+
+#[test]
+fn test_get_many_mut() {
+    let mut beap = Beap::from([1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    assert!(beap.get_many_mut([0, 10]).is_none());
+    assert!(beap.get_many_mut([3, 2, 3]).is_none());
+
+    // Two positions at once.
+    {
+        let mut vals = beap.get_many_mut([1, 9]).unwrap();
+        assert_eq!(vals.values(), [&9, &1]);
+        let [a, b] = vals.values_mut();
+        *a = 0;
+        *b = 20;
+    }
+    assert_eq!(beap.peek(), Some(&20));
+    assert_eq!(beap.tail(), Some(&0));
+
+    // Three positions at once.
+    {
+        let mut vals = beap.get_many_mut([0, 5, 8]).unwrap();
+        assert_eq!(vals.values(), [&20, &8, &2]);
+        for x in vals.values_mut() {
+            *x = -*x;
+        }
+    }
+    assert_eq!(beap.peek(), Some(&10));
+    assert_eq!(
+        beap.into_sorted_vec(),
+        vec![-20, -8, -2, 0, 3, 4, 5, 6, 7, 10]
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_get_many_mut_random() {
+    // Random tests against pop order
+    let mut rng = thread_rng();
+
+    for size in 3..=100 {
+        for _ in 0..10 {
+            let mut beap: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+
+            let mut positions = [0; 3];
+            loop {
+                for pos in positions.iter_mut() {
+                    *pos = rng.gen_range(0..size);
+                }
+                if positions[0] != positions[1]
+                    && positions[1] != positions[2]
+                    && positions[0] != positions[2]
+                {
+                    break;
+                }
+            }
+
+            {
+                let mut vals = beap.get_many_mut(positions).unwrap();
+                for x in vals.values_mut() {
+                    *x = rng.gen_range(-40..=40);
+                }
+            }
+
+            let mut expected: Vec<i64> = beap.iter().copied().collect();
+            expected.sort_unstable_by(|x, y| y.cmp(x));
+            for x in expected {
+                assert_eq!(beap.pop(), Some(x));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_replace_index() {
+    let mut beap = Beap::from([1, 2, 3, 4, 5, 6]);
+    assert_eq!(beap.replace_index(6, 0), None);
+    assert_eq!(beap.replace_index(0, 0), Some(6));
+    assert_eq!(beap.peek(), Some(&5));
+    assert_eq!(beap.replace_index(5, 7), Some(1));
+    assert_eq!(beap.peek(), Some(&7));
+    assert_eq!(beap.tail(), Some(&0));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_replace_index_random() {
+    // Random tests against a sorted Vec
+    let mut rng = thread_rng();
+
+    for size in 1..=100 {
+        let mut beap: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        let mut v: Vec<i64> = beap.iter().copied().collect();
+
+        for _ in 0..10 {
+            let pos = rng.gen_range(0..size);
+            let new = rng.gen_range(-40..=40);
+            let old = beap.get(pos).copied();
+            assert_eq!(beap.replace_index(pos, new), old);
+
+            let i = v.iter().position(|&x| Some(x) == old).unwrap();
+            v[i] = new;
+        }
+
+        v.sort_unstable();
+        for x in v.into_iter().rev() {
+            assert_eq!(beap.pop(), Some(x));
+        }
+    }
+}
+
+#[test]
+fn test_modify_index() {
+    let mut beap = Beap::from([1, 2, 3, 4, 5, 6]);
+    assert!(!beap.modify_index(6, |x| *x = 0));
+
+    // Increment an internal element up to the top.
+    let pos = beap.index(&3).unwrap();
+    assert!(beap.modify_index(pos, |x| *x += 10));
+    assert_eq!(beap.peek(), Some(&13));
+
+    // Decrease the maximum below everything else.
+    assert!(beap.modify_index(0, |x| *x = 0));
+    assert_eq!(beap.peek(), Some(&6));
+    assert_eq!(beap.tail(), Some(&0));
+
+    for x in [6, 5, 4, 2, 1, 0] {
+        assert_eq!(beap.pop(), Some(x));
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_modify_index_random() {
+    // Random tests against a sorted Vec
+    let mut rng = thread_rng();
+
+    for size in 1..=100 {
+        let mut beap: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+
+        for _ in 0..10 {
+            let pos = rng.gen_range(0..size);
+            let delta = rng.gen_range(-20..=20);
+            assert!(beap.modify_index(pos, |x| *x += delta));
+        }
+
+        let mut v: Vec<i64> = beap.iter().copied().collect();
+        v.sort_unstable();
+        for x in v.into_iter().rev() {
+            assert_eq!(beap.pop(), Some(x));
+        }
+    }
+}
+
+#[test]
+fn test_replace_root() {
+    let mut beap = Beap::new();
+    assert_eq!(beap.replace_root(2), None);
+    assert_eq!(beap.replace_root(1), Some(2));
+    assert_eq!(beap.len(), 1);
+
+    beap.extend([5, 3, 4]);
+    assert_eq!(beap.replace_root(10), Some(5));
+    assert_eq!(beap.peek(), Some(&10));
+    assert_eq!(beap.replace_root(0), Some(10));
+    assert_eq!(beap.peek(), Some(&4));
+    assert_eq!(beap.tail(), Some(&0));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_replace_root_random() {
+    // Random tests against sequential pop and push
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut beap: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        let mut beap2 = beap.clone();
+
+        for _ in 0..10 {
+            let item = rng.gen_range(-40..=40);
+            let expected = beap2.pop();
+            beap2.push(item);
+            assert_eq!(beap.replace_root(item), expected);
+            assert_eq!(beap.peek(), beap2.peek());
+        }
+
+        while let Some(x) = beap2.pop() {
+            assert_eq!(beap.pop(), Some(x));
+        }
+        assert!(beap.is_empty());
+    }
+}
+
+#[test]
+fn test_min_and_max() {
+    let mut beap = Beap::new();
+    assert_eq!(beap.min_and_max(), None);
+    beap.push(1);
+    assert_eq!(beap.min_and_max(), Some((&1, &1)));
+    beap.extend([4, 2, 8, 0]);
+    assert_eq!(beap.min_and_max(), Some((&0, &8)));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_min_and_max_random() {
+    // Random tests against tail and peek
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let beap: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        let expected = beap.tail().zip(beap.peek());
+        assert_eq!(beap.min_and_max(), expected);
+    }
+}
+
+#[test]
+fn test_remove_all() {
+    let mut beap = Beap::from([4, 2, 4, 1, 4, 3, 4, 4]);
+    assert_eq!(beap.remove_all(&0), 0);
+    assert_eq!(beap.remove_all(&4), 5);
+    assert_eq!(beap.len(), 3);
+    assert!(!beap.contains(&4));
+    assert_eq!(beap.pop(), Some(3));
+    assert_eq!(beap.pop(), Some(2));
+    assert_eq!(beap.pop(), Some(1));
+    assert_eq!(beap.pop(), None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_remove_all_random() {
+    // Random tests against a sorted Vec
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut beap: Beap<i64> = (0..size).map(|_| rng.gen_range(0..=5)).collect();
+        let mut v: Vec<i64> = beap.iter().copied().collect();
+
+        let val = rng.gen_range(0..=5);
+        let expected = v.iter().filter(|&&x| x == val).count();
+        v.retain(|&x| x != val);
+        v.sort_unstable();
+
+        assert_eq!(beap.remove_all(&val), expected);
+        for x in v.into_iter().rev() {
+            assert_eq!(beap.pop(), Some(x));
+        }
+        assert!(beap.is_empty());
+    }
+}
+
+#[test]
+fn test_count() {
+    let beap = Beap::from([2, 7, 2, 1, 2, 7]);
+    assert_eq!(beap.count(&2), 3);
+    assert_eq!(beap.count(&7), 2);
+    assert_eq!(beap.count(&1), 1);
+    assert_eq!(beap.count(&3), 0);
+    assert_eq!(Beap::new().count(&0), 0);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_count_random() {
+    // Random tests against Vec
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let v: Vec<i64> = (0..size).map(|_| rng.gen_range(0..=10)).collect();
+        let beap = Beap::from(v.clone());
+        for val in -1..=11 {
+            assert_eq!(beap.count(&val), v.iter().filter(|&&x| x == val).count());
+        }
+    }
+}
+
+#[test]
+fn test_extract_if() {
+    let mut beap = Beap::from([1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    let mut odds: Vec<i32> = beap.extract_if(|x| x % 2 == 1).collect();
+    odds.sort_unstable();
+    assert_eq!(odds, vec![1, 3, 5, 7, 9]);
+    assert_eq!(beap.len(), 4);
+    for x in [8, 6, 4, 2] {
+        assert_eq!(beap.pop(), Some(x));
+    }
+
+    // Dropping the iterator early keeps the rest.
+    let mut beap = Beap::from([1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    assert!(beap.extract_if(|x| x % 2 == 1).next().is_some());
+    assert_eq!(beap.len(), 8);
+    assert_eq!(beap.iter().filter(|&x| x % 2 == 1).count(), 4);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // leaks the elements on purpose
+fn test_extract_if_leak() {
+    // Nothing removed: the layout and the height are restored as they were.
+    let mut beap = Beap::from([1, 2, 3, 4, 5, 6, 7]);
+    let layout = beap.as_slice().to_vec();
+    assert_eq!(beap.extract_if(|_| false).next(), None);
+    assert_eq!(beap.as_slice(), layout.as_slice());
+    assert_eq!(beap.height, 4);
+
+    // A leaked iterator leaves the beap empty and usable.
+    let mut iter = beap.extract_if(|x| x % 2 == 1);
+    assert!(iter.next().is_some());
+    core::mem::forget(iter);
+    assert!(beap.is_empty());
+    assert_eq!(beap.height, 0);
+
+    for x in [3, 8, 1, 5] {
+        beap.push(x);
+    }
+    assert!(beap.is_valid());
+    assert_eq!(beap.into_sorted_vec(), vec![1, 3, 5, 8]);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_extract_if_random() {
+    // Random tests against Vec::retain
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut beap: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        let mut v: Vec<i64> = beap.iter().copied().collect();
+        let m = rng.gen_range(1..=4);
+
+        let mut extracted: Vec<i64> = beap.extract_if(|x| x % m == 0).collect();
+        extracted.sort_unstable();
+
+        let mut expected: Vec<i64> = v.iter().copied().filter(|x| x % m == 0).collect();
+        expected.sort_unstable();
+        assert_eq!(extracted, expected);
+
+        v.retain(|x| x % m != 0);
+        v.sort_unstable();
+        for x in v.into_iter().rev() {
+            assert_eq!(beap.pop(), Some(x));
+        }
+        assert!(beap.is_empty());
+    }
+}
+
+#[test]
+fn test_split_off() {
+    let mut a = Beap::from([3, 1, 4, 1, 5, 9, 2, 6]);
+    let mut b = a.split_off(&4);
+    for x in [4, 3, 2, 1, 1] {
+        assert_eq!(a.pop(), Some(x));
+    }
+    for x in [9, 6, 5] {
+        assert_eq!(b.pop(), Some(x));
+    }
+
+    let mut a = Beap::from([1, 2, 3]);
+    assert!(a.split_off(&3).is_empty());
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.split_off(&0).len(), 3);
+    assert!(a.is_empty());
+
+    let mut a = Beap::with_capacity(16);
+    a.extend([3, 1, 4, 1, 5]);
+    let b = a.split_off(&3);
+    assert_eq!(a.capacity(), 16);
+    assert_eq!(b.into_sorted_vec(), vec![4, 5]);
+    assert_eq!(a.into_sorted_vec(), vec![1, 1, 3]);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_split_off_random() {
+    // Random tests against a sorted Vec
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut a: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        let mut v: Vec<i64> = a.iter().copied().collect();
+        v.sort_unstable();
+
+        let threshold = rng.gen_range(-35..=35);
+        let mut b = a.split_off(&threshold);
+
+        let mut union = Vec::new();
+        while let Some(x) = b.pop() {
+            assert!(x > threshold);
+            union.push(x);
+        }
+        while let Some(x) = a.pop() {
+            assert!(x <= threshold);
+            union.push(x);
+        }
+        union.reverse();
+        assert_eq!(union, v);
+    }
+}
+
+#[test]
+fn test_truncate_to_largest() {
+    let mut beap = Beap::from([3, 1, 4, 1, 5, 9, 2, 6]);
+    beap.truncate_to_largest(8);
+    assert_eq!(beap.len(), 8);
+    beap.truncate_to_largest(100);
+    assert_eq!(beap.len(), 8);
+
+    beap.truncate_to_largest(3);
+    for x in [9, 6, 5] {
+        assert_eq!(beap.pop(), Some(x));
+    }
+    assert!(beap.is_empty());
+
+    let mut beap = Beap::from([3, 1, 4]);
+    beap.truncate_to_largest(0);
+    assert!(beap.is_empty());
+    beap.push(1);
+    assert_eq!(beap.peek(), Some(&1));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_truncate_to_largest_random() {
+    // Random tests against a sorted Vec
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut beap: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        let mut v: Vec<i64> = beap.iter().copied().collect();
+        v.sort_unstable_by(|x, y| y.cmp(x));
+
+        let k = rng.gen_range(0..=size + 5);
+        beap.truncate_to_largest(k);
+        v.truncate(k);
+
+        assert_eq!(beap.len(), v.len());
+        for x in v {
+            assert_eq!(beap.pop(), Some(x));
+        }
+    }
+}
+
+#[test]
+fn test_from_sorted_vec() {
+    let v = vec![9, 7, 7, 5, 3, 2, 2, 1, 0, -1];
+    let mut a = Beap::from_sorted_vec(v.clone());
+    let mut b = Beap::from(v.clone());
+    assert_eq!(a.as_slice(), b.as_slice());
+    for x in v {
+        assert_eq!(a.pop(), Some(x));
+        assert_eq!(b.pop(), Some(x));
+    }
+
+    let mut empty: Beap<i32> = Beap::from_sorted_vec(vec![]);
+    assert_eq!(empty.pop(), None);
+    empty.push(1);
+    assert_eq!(empty.peek(), Some(&1));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "not sorted in descending order")]
+fn test_from_sorted_vec_unsorted() {
+    Beap::from_sorted_vec(vec![3, 1, 2]);
+}
+
+#[test]
+fn test_from_sorted_desc_iter() {
+    let beap: Beap<i32> = Beap::from_sorted_desc_iter([]);
+    assert!(beap.is_empty());
+    assert_eq!(beap.height, 0);
+
+    let mut beap = Beap::from_sorted_desc_iter([9, 7, 7, 4, 2, 0, -3]);
+    assert_eq!(beap.height, 4);
+    assert!(beap.is_valid());
+    beap.push(5);
+    assert_eq!(beap.pop(), Some(9));
+    assert_eq!(beap.pop_tail(), Some(-3));
+    assert_eq!(beap.into_sorted_vec(), vec![0, 2, 4, 5, 7, 7]);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "not sorted in descending order")]
+fn test_from_sorted_desc_iter_unsorted() {
+    Beap::from_sorted_desc_iter(1..4);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_from_sorted_vec_random() {
+    // Random tests against From<Vec>
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut v: Vec<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        v.sort_unstable_by(|x, y| y.cmp(x));
+
+        let mut a = Beap::from_sorted_vec(v.clone());
+        let mut b = Beap::from(v);
+        for _ in 0..size / 2 {
+            let x = rng.gen_range(-30..=30);
+            a.push(x);
+            b.push(x);
+        }
+        while let Some(x) = b.pop() {
+            assert_eq!(a.pop(), Some(x));
+        }
+        assert!(a.is_empty());
+    }
+}
+
+#[test]
+fn test_from_sorted_vec_unchecked() {
+    let v: Vec<i32> = (0..20).rev().collect();
+    let mut beap = Beap::from_sorted_vec_unchecked(v.clone());
+    assert_eq!(beap.tail(), Some(&0));
+    beap.push(7);
+    assert_eq!(beap.len(), 21);
+
+    let mut expected = v;
+    expected.push(7);
+    expected.sort_unstable_by(|x, y| y.cmp(x));
+    for x in expected {
+        assert_eq!(beap.pop(), Some(x));
+    }
+
+    // An unsorted vector gives a corrupt but memory-safe beap with a consistent height.
+    let mut beap = Beap::from_sorted_vec_unchecked(vec![1, 5, 2, 9, 3, 7, 4]);
+    assert_eq!(beap.height, crate::height_for(7));
+    beap.push(6);
+    let mut popped = 0;
+    while beap.pop().is_some() {
+        popped += 1;
+    }
+    assert_eq!(popped, 8);
+}
+
+#[test]
+fn test_is_valid() {
+    assert!(Beap::<i32>::new().is_valid());
+
+    let mut beap = Beap::from([1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    assert!(beap.is_valid());
+
+    // Swap the maximum with an item from the last block.
+    beap.data.swap(0, 7);
+    assert!(!beap.is_valid());
+    beap.data.swap(0, 7);
+    assert!(beap.is_valid());
+
+    // Break the height.
+    beap.height += 1;
+    assert!(!beap.is_valid());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_is_valid_random() {
+    // Random tests against pushes, pops and corruption of the layout
+    let mut rng = thread_rng();
+
+    for size in 2..=100 {
+        let mut beap = Beap::new();
+        for _ in 0..size {
+            beap.push(rng.gen_range(0..1000));
+            assert!(beap.is_valid());
+        }
+        for _ in 0..size / 2 {
+            beap.pop();
+            assert!(beap.is_valid());
+        }
+
+        // Moving the smallest item to the top always breaks the beap.
+        let mut beap: Beap<i64> = (0..size).collect();
+        let last = beap.len() - 1;
+        beap.data.swap(0, last);
+        assert!(!beap.is_valid());
+    }
+}
+
+#[test]
+fn test_debug() {
+    let beap: Beap<i32> = Beap::new();
+    assert_eq!(format!("{:?}", beap), "Beap { data: [], height: 0 }");
+    assert_eq!(format!("{:#?}", beap), "Beap {\n}");
+
+    let beap = Beap::from([1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(
+        format!("{:?}", beap),
+        "Beap { data: [7, 6, 5, 4, 3, 2, 1], height: 4 }"
+    );
+    assert_eq!(
+        format!("{:#?}", beap),
+        "Beap {\n    [7],\n    [6, 5],\n    [4, 3, 2],\n    [1],\n}"
+    );
+}
+
+#[test]
+fn test_blocks() {
+    let beap: Beap<i32> = Beap::new();
+    assert_eq!(beap.blocks().len(), 0);
+    assert_eq!(beap.blocks().next(), None);
+
+    let beap = Beap::from([1, 2, 3, 4, 5, 6, 7]);
+    let mut blocks = beap.blocks();
+    assert_eq!(blocks.len(), 4);
+    assert_eq!(blocks.next(), Some(&[7][..]));
+    assert_eq!(blocks.next(), Some(&[6, 5][..]));
+    assert_eq!(blocks.next(), Some(&[4, 3, 2][..]));
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks.next(), Some(&[1][..]));
+    assert_eq!(blocks.next(), None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_blocks_random() {
+    // Random tests against as_slice
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let beap: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        assert_eq!(beap.blocks().len(), beap.height);
+
+        let mut concat = Vec::new();
+        for (i, block) in beap.blocks().enumerate() {
+            assert!(!block.is_empty() && block.len() <= i + 1);
+            concat.extend_from_slice(block);
+        }
+        assert_eq!(concat, beap.as_slice());
+    }
+}
+
+#[test]
+fn test_rank() {
+    let beap = Beap::from([2, 7, 2, 1, 2, 7]);
+    assert_eq!(beap.rank(&8), 0);
+    assert_eq!(beap.rank(&7), 2);
+    assert_eq!(beap.rank(&3), 2);
+    assert_eq!(beap.rank(&2), 5);
+    assert_eq!(beap.rank(&0), 6);
+    assert_eq!(Beap::new().rank(&0), 0);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_rank_random() {
+    // Random tests against Vec
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let v: Vec<i64> = (0..size).map(|_| rng.gen_range(0..=10)).collect();
+        let beap = Beap::from(v.clone());
+        for val in -1..=11 {
+            assert_eq!(beap.rank(&val), v.iter().filter(|&&x| x >= val).count());
+        }
+    }
+}
+
+#[test]
+fn test_find_all() {
+    let beap = Beap::from([2, 7, 2, 1, 2, 7]);
+    let idxs = beap.find_all(&2);
+    assert_eq!(idxs.len(), 3);
+    for i in idxs {
+        assert_eq!(beap[i], 2);
+    }
+    assert_eq!(beap.find_all(&7), vec![0, 1]);
+    assert!(beap.find_all(&3).is_empty());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_find_all_random() {
+    // Random tests against count and index
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let beap: Beap<i64> = (0..size).map(|_| rng.gen_range(0..=10)).collect();
+        for val in -1..=11 {
+            let idxs = beap.find_all(&val);
+            assert_eq!(idxs.len(), beap.count(&val));
+            assert!(idxs.iter().all(|&i| beap[i] == val));
+            if let Some(i) = beap.index(&val) {
+                assert!(idxs.contains(&i));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_extend_reserves() {
+    let mut expected: Vec<i32> = vec![1, 2, 3];
+    expected.reserve(10_000);
+
+    let mut beap = Beap::from(vec![1, 2, 3]);
+    beap.extend(0..10_000);
+    assert_eq!(beap.len(), 10_003);
+    assert_eq!(beap.capacity(), expected.capacity());
+
+    let items: Vec<i32> = (0..10_000).collect();
+    let mut beap = Beap::from(vec![1, 2, 3]);
+    beap.extend(&items);
+    assert_eq!(beap.capacity(), expected.capacity());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_append_merge_random() {
+    // Random tests against a sorted Vec, for both sorted and pushed layouts
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut a: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        let mut b = Beap::new();
+        for _ in 0..rng.gen_range(0..=100) {
+            b.push(rng.gen_range(-30..=30));
+        }
+        let mut v: Vec<i64> = a.iter().chain(b.iter()).copied().collect();
+        v.sort_unstable();
+
+        if size % 2 == 0 {
+            a.append(&mut b);
+        } else {
+            a.append_vec(&mut b.clone().into_vec());
+            b.clear();
+        }
+        assert!(b.is_empty());
+        assert!(a.is_valid());
+
+        for x in v.into_iter().rev() {
+            assert_eq!(a.pop(), Some(x));
+        }
+        assert!(a.is_empty());
+    }
+}
+
+#[test]
+fn test_merge() {
+    let merged = Beap::merge(Beap::from([1, 4, 2]), Beap::from([3, 0]));
+    assert_eq!(merged.len(), 5);
+    assert_eq!(merged.peek(), Some(&4));
+    assert_eq!(merged.into_sorted_vec(), vec![0, 1, 2, 3, 4]);
+
+    let mut a = Beap::with_capacity(10);
+    a.extend([1, 4, 2]);
+    let merged = Beap::merge(a, Beap::from([3, 0]));
+    assert_eq!(merged.capacity(), 10);
+
+    let merged = Beap::merge(Beap::new(), Beap::from([2, 1]));
+    assert_eq!(merged.into_sorted_vec(), vec![1, 2]);
+    assert!(Beap::<i32>::merge(Beap::new(), Beap::new()).is_empty());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_merge_random() {
+    // Random tests against append
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let a: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        let mut b = Beap::new();
+        for _ in 0..rng.gen_range(0..=100) {
+            b.push(rng.gen_range(-30..=30));
+        }
+
+        let mut appended = a.clone();
+        appended.append(&mut b.clone());
+        let mut merged = Beap::merge(a, b);
+
+        assert_eq!(merged.len(), appended.len());
+        assert_eq!(merged.peek(), appended.peek());
+        while let Some(x) = appended.pop() {
+            assert_eq!(merged.pop(), Some(x));
+        }
+        assert!(merged.is_empty());
+    }
+}
+
+#[test]
+fn test_min_beap() {
+    let mut beap = MinBeap::new();
+    assert_eq!(beap.peek(), None);
+    assert_eq!(beap.pop(), None);
+
+    beap.extend([5, 1, 8, 3, 2]);
+    assert_eq!(beap.len(), 5);
+    assert_eq!(beap.peek(), Some(&1));
+    assert_eq!(beap.tail(), Some(&8));
+    assert!(beap.contains(&3));
+    assert!(beap.remove(&3));
+    assert!(!beap.contains(&3));
+
+    for x in [1, 2, 5, 8] {
+        assert_eq!(beap.pop(), Some(x));
+    }
+    assert!(beap.is_empty());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_min_beap_random() {
+    // Random tests against a sorted Vec
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let v: Vec<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        let mut sorted = v.clone();
+        sorted.sort_unstable();
+
+        let beap = MinBeap::from(v.clone());
+        assert_eq!(beap.clone().into_sorted_vec(), sorted);
+
+        let mut beap = beap;
+        for x in sorted {
+            assert_eq!(beap.pop(), Some(x));
+        }
+        assert!(beap.is_empty());
+    }
+}
+
+#[test]
+fn test_into_min() {
+    let mut beap: Beap<i32> = Beap::new_max();
+    beap.extend([5, 1, 8, 3, 2]);
+    assert_eq!(beap.peek(), Some(&8));
+
+    let mut min = beap.into_min();
+    assert_eq!(min.len(), 5);
+    assert_eq!(min.tail(), Some(&8));
+    for x in [1, 2, 3, 5, 8] {
+        assert_eq!(min.pop(), Some(x));
+    }
+    assert_eq!(min.pop(), None);
+
+    let mut min = Beap::new_min();
+    min.push(2);
+    min.push(1);
+    assert_eq!(min.peek(), Some(&1));
+    assert!(Beap::<i32>::new().into_min().is_empty());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_into_min_random() {
+    // Random tests against a sorted Vec
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let v: Vec<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        let mut sorted = v.clone();
+        sorted.sort_unstable();
+
+        let mut min = Beap::from(v).into_min();
+        for x in sorted {
+            assert_eq!(min.pop(), Some(x));
+        }
+        assert!(min.is_empty());
+    }
+}
+
+#[test]
+fn test_push_bounded() {
+    let mut beap = Beap::new();
+    assert_eq!(beap.push_bounded(1, 0), Some(1));
+    assert!(beap.is_empty());
+
+    for x in [4, 8, 1] {
+        assert_eq!(beap.push_bounded(x, 3), None);
+    }
+    assert_eq!(beap.push_bounded(0, 3), Some(0));
+    assert_eq!(beap.push_bounded(1, 3), Some(1));
+    assert_eq!(beap.push_bounded(5, 3), Some(1));
+    assert_eq!(beap.push_bounded(9, 3), Some(4));
+    assert_eq!(beap.len(), 3);
+    for x in [9, 8, 5] {
+        assert_eq!(beap.pop(), Some(x));
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_push_bounded_random() {
+    // Random tests against a sorted Vec
+    let mut rng = thread_rng();
+
+    for cap in 0..=20 {
+        let stream: Vec<i64> = (0..1000).map(|_| rng.gen_range(-500..=500)).collect();
+        let mut beap = Beap::new();
+        for &x in &stream {
+            beap.push_bounded(x, cap);
+        }
+
+        let mut expected = stream;
+        expected.sort_unstable_by(|x, y| y.cmp(x));
+        expected.truncate(cap);
+
+        assert_eq!(beap.len(), expected.len());
+        for x in expected {
+            assert_eq!(beap.pop(), Some(x));
+        }
+    }
+}
+
+#[test]
+fn test_into_iter_size_hint() {
+    let beap = Beap::from([1, 2, 3, 4, 5]);
+    let mut iter = beap.into_iter();
+    assert_eq!(iter.size_hint(), (5, Some(5)));
+    iter.next();
+    iter.next_back();
+    assert_eq!(iter.len(), 3);
+
+    let empty: Beap<i32> = Beap::new();
+    assert_eq!(empty.into_iter().size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn test_drain_as_slice() {
+    let mut beap = Beap::from([1, 2, 3, 4]);
+    let mut drain = beap.drain();
+    assert_eq!(drain.as_slice(), &[4, 3, 2, 1]);
+    assert_eq!(drain.next(), Some(4));
+    assert_eq!(drain.as_slice(), &[3, 2, 1]);
+    assert_eq!(drain.next_back(), Some(1));
+    assert_eq!(drain.as_slice(), &[3, 2]);
+    drop(drain);
+    assert!(beap.is_empty());
+}
+
+#[test]
+fn test_drain_partial_and_forget() {
+    let mut beap = Beap::from([5, 3, 1, 4, 2]);
+    let mut drain = beap.drain();
+    assert_eq!(drain.next(), Some(5));
+    drop(drain);
+    assert!(beap.is_empty());
+    assert_eq!(beap.height, 0);
+    assert!(beap.is_valid());
+
+    beap.extend([7, 9, 8]);
+    let mut drain = beap.drain();
+    assert_eq!(drain.next(), Some(9));
+    core::mem::forget(drain);
+    assert!(beap.is_empty());
+    assert_eq!(beap.height, 0);
+
+    beap.push(6);
+    beap.push(10);
+    assert_eq!(beap.height, 2);
+    assert_eq!(beap.into_sorted_vec(), vec![6, 10]);
+}
+
+#[test]
+fn test_iter_as_slice() {
+    let beap = Beap::from([1, 2, 3, 4]);
+    let mut iter = beap.iter();
+    assert_eq!(iter.as_slice(), beap.as_slice());
+    assert_eq!(iter.next(), Some(&4));
+    assert_eq!(iter.as_slice(), &[3, 2, 1]);
+    assert_eq!(iter.next_back(), Some(&1));
+    assert_eq!(iter.as_slice(), &[3, 2]);
+
+    // The slice outlives the iterator.
+    let rest = {
+        let mut iter = beap.iter();
+        iter.next();
+        iter.as_slice()
+    };
+    assert_eq!(rest, &[3, 2, 1]);
+}
+
+#[test]
+fn test_as_ref() {
+    let beap = Beap::from([3, 1, 2]);
+    let slice: &[i32] = beap.as_ref();
+    assert_eq!(slice, beap.as_slice());
+}
+
+#[test]
+fn test_try_from_vec() {
+    assert!(Beap::<i32>::try_from_vec(vec![]).unwrap().is_empty());
+
+    let mut beap = Beap::try_from_vec(vec![5, 4, 3, 2, 1]).unwrap();
+    assert_eq!(beap.tail(), Some(&1));
+    beap.push(6);
+    assert_eq!(beap.peek(), Some(&6));
+
+    // A valid layout which is not sorted.
+    let beap = Beap::try_from_vec(vec![9, 7, 8, 1, 6, 5]).unwrap();
+    assert_eq!(beap.peek(), Some(&9));
+
+    let scrambled = vec![3, 5, 1, 4, 2];
+    assert_eq!(
+        Beap::try_from_vec(scrambled.clone()).unwrap_err(),
+        scrambled
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_try_from_vec_random() {
+    // Random tests against is_valid
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut beap = Beap::new();
+        for _ in 0..size {
+            beap.push(rng.gen_range(-30..=30));
+        }
+        let vec = beap.clone().into_vec();
+        let mut adopted = Beap::try_from_vec(vec).unwrap();
+        while let Some(x) = beap.pop() {
+            assert_eq!(adopted.pop(), Some(x));
+        }
+
+        let vec: Vec<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        let valid = vec.windows(2).all(|w| w[0] >= w[1]);
+        match Beap::try_from_vec(vec.clone()) {
+            Ok(beap) => assert!(beap.is_valid()),
+            Err(back) => {
+                assert!(!valid);
+                assert_eq!(back, vec);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_beap_macro() {
+    let empty: Beap<i32> = crate::beap![];
+    assert!(empty.is_empty());
+
+    let mut beap = crate::beap![3, 1, 4, 1, 5,];
+    assert!(beap.is_valid());
+    for x in [5, 4, 3, 1, 1] {
+        assert_eq!(beap.pop(), Some(x));
+    }
+
+    let beap = crate::beap![7; 4];
+    assert!(beap.is_valid());
+    assert_eq!(beap.count(&7), 4);
+    assert_eq!(beap.len(), 4);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_sample_random() {
+    // Statistical test: every item is sampled roughly equally often
+    let mut rng = thread_rng();
+    let beap: Beap<usize> = (0..10).collect();
+    let mut counts = [0; 10];
+    for _ in 0..100_000 {
+        counts[*beap.sample(&mut rng).unwrap()] += 1;
+    }
+    for count in counts {
+        assert!((9_000..=11_000).contains(&count), "{:?}", counts);
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_tail_with_index_random() {
+    // Random tests against tail
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let beap: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        match beap.tail_with_index() {
+            Some((idx, x)) => {
+                assert_eq!(beap.get(idx), Some(x));
+                assert_eq!(beap.tail(), Some(x));
+            }
+            None => assert!(beap.is_empty()),
+        }
+    }
+}
+
+#[test]
+fn test_height_for_sizes() {
+    // Block `b` holds `b` elements, so a full beap of height `h` has `h * (h + 1) / 2` ones.
+    for h in 1..=1000 {
+        let full = h * (h + 1) / 2;
+        assert_eq!(crate::height_for(full), h);
+        assert_eq!(crate::height_for(full + 1), h + 1);
+        assert_eq!(crate::height_for(full - h + 1), h);
+    }
+
+    let mut beap = Beap::with_capacity_for(10);
+    assert_eq!(beap.height, 0);
+    assert!(beap.capacity() >= 10);
+    beap.push(1);
+    assert_eq!(beap.height, 1);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_height_for() {
+    assert_eq!(crate::height_for(0), 0);
+
+    let mut beap = Beap::new();
+    for len in 1..=100_000 {
+        beap.push(len);
+        assert_eq!(beap.height, crate::height_for(len));
+    }
+    for len in (0..100_000).rev() {
+        beap.pop();
+        assert_eq!(beap.height, crate::height_for(len));
+    }
+}
+
+#[test]
+fn test_block_of() {
+    let beap: Beap<i32> = Beap::new();
+    for pos in 0..=10_000 {
+        let b = crate::core::block_of(pos);
+        assert_eq!(b, ((2 * (pos + 1)) as f64).sqrt().round() as usize);
+        let (start, end) = beap.span(b).unwrap();
+        assert!(start <= pos && pos <= end);
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_from_iter_filter() {
+    // A `Filter` has no useful lower bound in its `size_hint`.
+    let mut beap: Beap<u32> = (0..100_000).filter(|x| x % 3 == 0).collect();
+    assert_eq!(beap.len(), 33_334);
+    assert!(beap.is_valid());
+    for x in (0..100_000).filter(|x| x % 3 == 0).rev() {
+        assert_eq!(beap.pop(), Some(x));
+    }
+    assert!(beap.is_empty());
+}
+
+#[test]
+fn test_clear_and_shrink() {
+    let mut beap: Beap<i32> = (0..10_000).collect();
+    beap.clear();
+    assert!(beap.capacity() >= 10_000);
+
+    beap.extend(0..10_000);
+    beap.clear_and_shrink();
+    assert!(beap.is_empty());
+    assert_eq!(beap.capacity(), 0);
+
+    beap.push(1);
+    assert_eq!(beap.peek(), Some(&1));
+}
+
+#[test]
+fn test_raw_parts_stale_height() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    // A wrong height only makes the results unspecified: every access is bounds-checked,
+    // so the operations below either panic or return some value.
+    for height in [0, 1, 2, 5, 100] {
+        let data = vec![9, 7, 8, 3, 5, 6, 1];
+        let mut beap = Beap::from_raw_parts(data, height);
+        let _ = catch_unwind(AssertUnwindSafe(|| {
+            beap.push(10);
+            beap.push(0);
+            let _ = beap.tail();
+            let _ = beap.pop();
+            let _ = beap.remove_index(3);
+            let _ = beap.pop_tail();
+        }));
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_raw_parts_random() {
+    // Random round-trip tests against the original beap
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut beap = Beap::new();
+        for _ in 0..size {
+            beap.push(rng.gen_range(-30..=30));
+        }
+
+        let (data, height) = beap.clone().into_raw_parts();
+        assert_eq!(data, beap.as_slice());
+        assert_eq!(height, beap.height);
+
+        let mut restored = Beap::from_raw_parts(data, height);
+        assert!(restored.is_valid());
+        while let Some(x) = beap.pop() {
+            assert_eq!(restored.pop(), Some(x));
+        }
+        assert!(restored.is_empty());
+    }
+}
+
+#[test]
+fn test_push_iter_capacity() {
+    let mut beap = Beap::from([5, 3]);
+    beap.push_iter(0..10_000);
+    assert!(beap.capacity() >= 10_002);
+    assert!(beap.is_valid());
+
+    // The reserved space is enough, so the buffer is not moved.
+    let mut beap = Beap::from([5, 3]);
+    beap.reserve(10_000);
+    let ptr = beap.as_slice().as_ptr();
+    beap.push_iter(0..10_000);
+    assert_eq!(beap.as_slice().as_ptr(), ptr);
+
+    // Iterators without a lower bound still work.
+    beap.push_iter((0..100).filter(|x| x % 10 == 0));
+    assert_eq!(beap.len(), 10_012);
+    let mut expected: Vec<i32> = (0..10_000)
+        .chain([3, 5])
+        .chain((0..100).step_by(10))
+        .collect();
+    expected.sort_unstable();
+    assert_eq!(beap.into_sorted_vec(), expected);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_push_iter_random() {
+    // Random tests against extend
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut a: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        let mut b = a.clone();
+        let items: Vec<i64> = (0..rng.gen_range(0..=100))
+            .map(|_| rng.gen_range(-30..=30))
+            .collect();
+
+        a.push_iter(items.iter().copied());
+        b.extend(items);
+        assert!(a.is_valid());
+
+        assert_eq!(a.len(), b.len());
+        while let Some(x) = b.pop() {
+            assert_eq!(a.pop(), Some(x));
+        }
+    }
+}
+
+#[test]
+fn test_dedup() {
+    let mut beap = Beap::from([2, 7, 2, 1, 2, 7, 5]);
+    beap.dedup();
+    assert!(beap.is_valid());
+    assert_eq!(beap.into_sorted_vec(), vec![1, 2, 5, 7]);
+
+    let mut beap = Beap::from([15, 3, 12, 21, 7, 28]);
+    beap.dedup_by_key(|x| *x / 10);
+    assert!(beap.is_valid());
+    assert_eq!(beap.into_sorted_vec(), vec![7, 15, 28]);
+
+    let mut beap = Beap::from([1, 2, 3, 10, 11, 20]);
+    beap.dedup_by(|a, b| *b - *a < 5);
+    assert!(beap.is_valid());
+    assert_eq!(beap.into_sorted_vec(), vec![3, 11, 20]);
+
+    // The closures may change the items, the beap must stay valid anyway.
+    let mut beap = Beap::from([1, 2, 3, 4, 5]);
+    beap.dedup_by(|a, _| {
+        *a += 100;
+        false
+    });
+    assert!(beap.is_valid());
+    assert_eq!(beap.peek(), Some(&104));
+    assert_eq!(beap.into_sorted_vec(), vec![5, 101, 102, 103, 104]);
+
+    let mut beap = Beap::from([1, 2, 3, 4, 5]);
+    beap.dedup_by_key(|x| {
+        *x = 10 - *x;
+        *x
+    });
+    assert!(beap.is_valid());
+    assert_eq!(beap.len(), 5);
+
+    let mut beap: Beap<i32> = Beap::new();
+    beap.dedup();
+    assert!(beap.is_empty());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_dedup_random() {
+    // Random tests against a HashSet
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let mut beap: Beap<i64> = (0..size).map(|_| rng.gen_range(-10..=10)).collect();
+        let set: HashSet<i64> = beap.iter().copied().collect();
+
+        beap.dedup();
+        assert!(beap.is_valid());
+        assert_eq!(beap.len(), set.len());
+        assert_eq!(beap.iter().copied().collect::<HashSet<i64>>(), set);
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_to_sorted_vec_random() {
+    // Random tests against into_sorted_vec
+    let mut rng = thread_rng();
+
+    for size in 0..=100 {
+        let beap: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        let before = beap.as_slice().to_vec();
+
+        let sorted = beap.to_sorted_vec();
+        assert!(sorted.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(beap.as_slice(), before);
+        assert_eq!(sorted, beap.into_sorted_vec());
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_swap_indices_random() {
+    // Random tests against a sorted Vec
+    let mut rng = thread_rng();
+
+    for size in 1..=100 {
+        let mut beap: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+        let mut v: Vec<i64> = beap.iter().copied().collect();
+        v.sort_unstable();
+
+        assert!(!beap.swap_indices(0, size));
+        for _ in 0..10 {
+            let i = rng.gen_range(0..size);
+            let j = rng.gen_range(0..size);
+            assert!(beap.swap_indices(i, j));
+            assert!(beap.is_valid());
+        }
+
+        for x in v.into_iter().rev() {
+            assert_eq!(beap.pop(), Some(x));
+        }
+    }
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "less than the current one")]
+fn test_increase_key_smaller() {
+    let mut beap = Beap::from([1, 2, 3]);
+    beap.increase_key(0, 0);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "greater than the current one")]
+fn test_decrease_key_greater() {
+    let mut beap = Beap::from([1, 2, 3]);
+    beap.decrease_key(2, 5);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_increase_decrease_key_random() {
+    // Random tests against a sorted Vec
+    let mut rng = thread_rng();
+
+    for size in 1..=100 {
+        let mut beap: Beap<i64> = (0..size).map(|_| rng.gen_range(-30..=30)).collect();
+
+        assert!(!beap.increase_key(size, 0));
+        assert!(!beap.decrease_key(size, 0));
+        for _ in 0..10 {
+            let pos = rng.gen_range(0..size);
+            let delta = rng.gen_range(0..=20);
+            let old = beap[pos];
+            if rng.gen_bool(0.5) {
+                assert!(beap.increase_key(pos, old + delta));
+            } else {
+                assert!(beap.decrease_key(pos, old - delta));
+            }
+            assert!(beap.is_valid());
+        }
+
+        let mut v: Vec<i64> = beap.iter().copied().collect();
+        v.sort_unstable();
+        for x in v.into_iter().rev() {
+            assert_eq!(beap.pop(), Some(x));
+        }
+    }
+}
+
+#[test]
+fn test_indexed_beap() {
+    let mut beap = IndexedBeap::new();
+    assert_eq!(beap.pop(), None);
+
+    let keys: Vec<_> = [5, 1, 4, 2, 3]
+        .into_iter()
+        .map(|x| beap.insert(x))
+        .collect();
+    assert_eq!(beap.len(), 5);
+    assert_eq!(beap.peek(), Some((keys[0], &5)));
+    for (&key, x) in keys.iter().zip([5, 1, 4, 2, 3]) {
+        assert_eq!(beap.get(key), Some(&x));
+    }
+
+    assert_eq!(beap.change_priority(keys[1], 10), Some(1));
+    assert_eq!(beap.peek(), Some((keys[1], &10)));
+    assert_eq!(beap.change_priority(keys[1], 0), Some(10));
+    assert_eq!(beap.remove(keys[2]), Some(4));
+    assert_eq!(beap.remove(keys[2]), None);
+    assert_eq!(beap.change_priority(keys[2], 7), None);
+
+    // A new item never takes over a stale key.
+    let key = beap.insert(4);
+    assert_ne!(key, keys[2]);
+    assert!(!beap.contains_key(keys[2]));
+
+    // The scratch buffer is neither shown nor cloned.
+    assert!(!format!("{:?}", beap).contains("touched"));
+    let mut cloned = beap.clone();
+    assert_eq!(cloned.pop(), Some((keys[0], 5)));
+    assert_eq!(cloned.peek(), Some((key, &4)));
+
+    assert_eq!(beap.pop(), Some((keys[0], 5)));
+    assert_eq!(beap.pop(), Some((key, 4)));
+    assert_eq!(beap.pop(), Some((keys[4], 3)));
+    assert_eq!(beap.pop(), Some((keys[3], 2)));
+    assert_eq!(beap.pop(), Some((keys[1], 0)));
+    assert!(beap.is_empty());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_indexed_beap_random() {
+    // Random tests against a Vec of the live keys
+    let mut rng = thread_rng();
+
+    for _ in 0..20 {
+        let mut beap = IndexedBeap::new();
+        let mut live = Vec::new();
+
+        for _ in 0..1000 {
+            match rng.gen_range(0..4) {
+                0 | 1 => {
+                    let x = rng.gen_range(-100..=100);
+                    live.push((beap.insert(x), x));
+                }
+                2 if !live.is_empty() => {
+                    let i = rng.gen_range(0..live.len());
+                    let x = rng.gen_range(-100..=100);
+                    assert_eq!(beap.change_priority(live[i].0, x), Some(live[i].1));
+                    live[i].1 = x;
+                }
+                3 if !live.is_empty() => {
+                    if rng.gen_bool(0.5) {
+                        let (key, x) = beap.pop().unwrap();
+                        let i = live.iter().position(|&(k, _)| k == key).unwrap();
+                        assert_eq!(live.swap_remove(i).1, x);
+                        assert!(live.iter().all(|&(_, y)| y <= x));
+                    } else {
+                        let i = rng.gen_range(0..live.len());
+                        let (key, x) = live.swap_remove(i);
+                        assert_eq!(beap.remove(key), Some(x));
+                    }
+                }
+                _ => {}
+            }
+
+            assert_eq!(beap.len(), live.len());
+            for &(key, x) in &live {
+                assert_eq!(beap.get(key), Some(&x));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_entry() {
+    let mut beap = Beap::from([5, 1, 4, 2]);
+
+    match beap.entry(&4) {
+        Entry::Occupied(entry) => {
+            assert_eq!(*entry.get(), 4);
+            assert!(entry.index() < 4);
+            *entry.get_mut() = 10;
+        }
+        Entry::Vacant(_) => panic!("4 is present"),
+    }
+    assert_eq!(beap.peek(), Some(&10));
+
+    match beap.entry(&3) {
+        Entry::Occupied(_) => panic!("3 is absent"),
+        Entry::Vacant(entry) => entry.insert(3),
+    }
+    assert!(beap.contains(&3));
+
+    match beap.entry(&1) {
+        Entry::Occupied(entry) => assert_eq!(entry.remove(), 1),
+        Entry::Vacant(_) => panic!("1 is present"),
+    }
+    assert!(!beap.contains(&1));
+
+    *beap.entry(&7).or_insert(7) -= 7;
+    *beap.entry(&5).or_insert(5) += 10;
+    assert_eq!(beap.into_sorted_vec(), vec![0, 2, 3, 10, 15]);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_entry_random() {
+    // Random tests against a Vec updated the same way
+    let mut rng = thread_rng();
+
+    for _ in 0..100 {
+        let len = rng.gen_range(0..100);
+        let mut v: Vec<i64> = (0..len).map(|_| rng.gen_range(-50..=50)).collect();
+        let mut beap = Beap::from(v.clone());
+
+        for _ in 0..50 {
+            let val = rng.gen_range(-60..=60);
+            let new = rng.gen_range(-60..=60);
+            *beap.entry(&val).or_insert(val) = new;
+            match v.iter().position(|&x| x == val) {
+                Some(i) => v[i] = new,
+                None => v.push(new),
+            }
+        }
+
+        assert_pops_sorted(beap, v);
+    }
+}
+
+#[test]
+fn test_pop_if() {
+    let mut beap: Beap<i32> = Beap::new();
+    assert_eq!(beap.pop_if(|_| true), None);
+
+    beap.extend([4, 8, 1, 6]);
+    assert_eq!(beap.pop_if(|&x| x % 2 == 1), None);
+    assert_eq!(beap.len(), 4);
+    assert_eq!(beap.pop_if(|&x| x % 2 == 0), Some(8));
+    assert_eq!(beap.pop_if(|&x| x > 5), Some(6));
+    assert_eq!(beap.pop_if(|&x| x > 5), None);
+    assert_eq!(beap.into_sorted_vec(), vec![1, 4]);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_pop_if_random() {
+    // Random tests against a sorted Vec
+    let mut rng = thread_rng();
+
+    for _ in 0..100 {
+        let len = rng.gen_range(0..200);
+        let mut v: Vec<i64> = (0..len).map(|_| rng.gen_range(-100..=100)).collect();
+        let mut beap = Beap::from(v.clone());
+        let threshold = rng.gen_range(-100..=100);
+
+        v.sort_unstable();
+        while let Some(x) = beap.pop_if(|&x| x > threshold) {
+            assert_eq!(x, v.pop().unwrap());
+        }
+        assert!(v.last().is_none_or(|&x| x <= threshold));
+        assert_pops_sorted(beap, v);
+    }
+}
+
+#[test]
+fn test_pop_tail_if() {
+    let mut beap: Beap<i32> = Beap::new();
+    assert_eq!(beap.pop_tail_if(|_| true), None);
+
+    beap.extend([4, 8, 1, 6]);
+    assert_eq!(beap.pop_tail_if(|&x| x % 2 == 0), None);
+    assert_eq!(beap.len(), 4);
+    assert_eq!(beap.pop_tail_if(|&x| x % 2 == 1), Some(1));
+    assert_eq!(beap.pop_tail_if(|&x| x < 5), Some(4));
+    assert_eq!(beap.pop_tail_if(|&x| x < 5), None);
+    assert_eq!(beap.into_sorted_vec(), vec![6, 8]);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_pop_tail_if_random() {
+    // Random tests against a reverse-sorted Vec
+    let mut rng = thread_rng();
+
+    for _ in 0..100 {
+        let len = rng.gen_range(0..200);
+        let mut v: Vec<i64> = (0..len).map(|_| rng.gen_range(-100..=100)).collect();
+        let mut beap = Beap::from(v.clone());
+        let threshold = rng.gen_range(-100..=100);
+
+        v.sort_unstable_by(|a, b| b.cmp(a));
+        while let Some(x) = beap.pop_tail_if(|&x| x < threshold) {
+            assert_eq!(x, v.pop().unwrap());
+        }
+        assert!(v.last().is_none_or(|&x| x >= threshold));
+        assert_pops_sorted(beap, v);
+    }
+}
+
+#[test]
+fn test_in_range() {
+    let beap: Beap<i32> = Beap::new();
+    assert_eq!(beap.count_in_range(&i32::MIN, &i32::MAX), 0);
+    assert!(!beap.any_in_range(&i32::MIN, &i32::MAX));
+
+    let beap = Beap::from([7, 2, 9, 2, 4, 0]);
+    assert_eq!(beap.count_in_range(&2, &4), 3);
+    assert_eq!(beap.count_in_range(&i32::MIN, &i32::MAX), 6);
+    assert_eq!(beap.count_in_range(&9, &9), 1);
+    assert_eq!(beap.count_in_range(&5, &6), 0);
+    assert_eq!(beap.count_in_range(&4, &2), 0);
+    assert!(beap.any_in_range(&8, &100));
+    assert!(!beap.any_in_range(&10, &100));
+    assert!(!beap.any_in_range(&9, &0));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_in_range_random() {
+    // Random tests against a Vec reference
+    let mut rng = thread_rng();
+
+    for _ in 0..100 {
+        let len = rng.gen_range(0..200);
+        let v: Vec<i64> = (0..len).map(|_| rng.gen_range(-100..=100)).collect();
+        let beap = Beap::from(v.clone());
+
+        for _ in 0..20 {
+            let lo = rng.gen_range(-110..=110);
+            let hi = rng.gen_range(-110..=110);
+            let expected = v.iter().filter(|&&x| lo <= x && x <= hi).count();
+            assert_eq!(beap.count_in_range(&lo, &hi), expected);
+            assert_eq!(beap.any_in_range(&lo, &hi), expected > 0);
+        }
+    }
+}
+
+#[test]
+fn test_clone_into_vec() {
+    let mut buf = vec![10, 20, 30, 40, 50, 60];
+    let ptr = buf.as_ptr();
+
+    Beap::<i32>::new().clone_into_vec(&mut buf);
+    assert!(buf.is_empty());
+
+    let beap = Beap::from([4, 1, 5, 2, 3]);
+    beap.clone_into_vec(&mut buf);
+    assert_eq!(buf.as_slice(), beap.as_slice());
+    assert_eq!(buf.as_ptr(), ptr);
+
+    let copy = Beap::try_from_vec(buf).unwrap();
+    assert_eq!(copy.into_vec(), beap.into_vec());
+}
+
+#[test]
+fn test_drain_range() {
+    let mut beap = Beap::from([1, 2, 3, 4, 5, 6, 7]);
+
+    assert!(beap.drain_range(3..3).is_empty());
+    assert_eq!(beap.len(), 7);
+
+    let mut drained = beap.drain_range(2..=4);
+    drained.sort_unstable();
+    assert_eq!(drained, [3, 4, 5]);
+    assert!(beap.is_valid());
+    assert_eq!(beap.height, 3);
+    assert_eq!(beap.clone().into_sorted_vec(), [1, 2, 6, 7]);
+
+    let mut drained = beap.drain_range(..);
+    drained.sort_unstable();
+    assert_eq!(drained, [1, 2, 6, 7]);
+    assert!(beap.is_empty());
+    assert_eq!(beap.height, 0);
+    assert_eq!(beap.pop(), None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_drain_range_random() {
+    // Random tests against Vec::drain
+    let mut rng = thread_rng();
+
+    for _ in 0..100 {
+        let len = rng.gen_range(0..200);
+        let v: Vec<i64> = (0..len).map(|_| rng.gen_range(-100..=100)).collect();
+        let mut beap = Beap::from(v);
+
+        let start = rng.gen_range(0..=len);
+        let end = rng.gen_range(start..=len);
+        let mut expected = beap.as_slice().to_vec();
+        let mut drained_expected: Vec<i64> = expected.drain(start..end).collect();
+        let mut drained = beap.drain_range(start..end);
+
+        drained.sort_unstable();
+        drained_expected.sort_unstable();
+        assert_eq!(drained, drained_expected);
+        assert_pops_sorted(beap, expected);
+    }
+}
+
+#[test]
+fn test_select_nth() {
+    let mut beap: Beap<i32> = Beap::new();
+    assert_eq!(beap.select_nth(0), None);
+
+    beap.extend([5, 9, 2, 7, 2, 0]);
+    assert_eq!(beap.select_nth(0), Some(&0));
+    assert_eq!(beap.select_nth(1), Some(&2));
+    assert_eq!(beap.select_nth(2), Some(&2));
+    assert_eq!(beap.select_nth(5), Some(&9));
+    assert_eq!(beap.select_nth(6), None);
+    assert!(beap.is_valid());
+    assert_eq!(beap.len(), 6);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_select_nth_random() {
+    // Random tests against a sorted Vec
+    let mut rng = thread_rng();
+
+    for _ in 0..100 {
+        let len = rng.gen_range(1..200);
+        let mut v: Vec<i64> = (0..len).map(|_| rng.gen_range(-100..=100)).collect();
+        let mut beap = Beap::from(v.clone());
+        v.sort_unstable();
+
+        let n = rng.gen_range(0..len);
+        assert_eq!(beap.select_nth(n), Some(&v[n]));
+
+        // The beap stays usable afterwards
+        let x = rng.gen_range(-100..=100);
+        beap.push(x);
+        v.push(x);
+        assert_pops_sorted(beap, v);
+    }
+}
+
+#[test]
+fn test_into_iter_sorted() {
+    let beap = Beap::from([3, 1, 4, 1, 5, 9, 2]);
+    let mut iter = beap.into_iter_sorted();
+    assert_eq!(iter.len(), 7);
+
+    // Walk inward from both ends until they meet.
+    assert_eq!(iter.next(), Some(9));
+    assert_eq!(iter.next_back(), Some(1));
+    assert_eq!(iter.next(), Some(5));
+    assert_eq!(iter.next_back(), Some(1));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next(), Some(4));
+    assert_eq!(iter.next_back(), Some(2));
+    assert_eq!(iter.next_back(), Some(3));
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    let beap: Beap<i32> = Beap::new();
+    assert_eq!(beap.into_iter_sorted().next(), None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_into_iter_sorted_random() {
+    // Random tests against a sorted VecDeque, consumed from both ends
+    let mut rng = thread_rng();
+
+    for _ in 0..100 {
+        let len = rng.gen_range(0..200);
+        let mut v: Vec<i64> = (0..len).map(|_| rng.gen_range(-100..=100)).collect();
+        let mut iter = Beap::from(v.clone()).into_iter_sorted();
+        v.sort_unstable();
+        let mut v = std::collections::VecDeque::from(v);
+
+        while !v.is_empty() {
+            assert_eq!(iter.len(), v.len());
+            if rng.gen_bool(0.5) {
+                assert_eq!(iter.next(), v.pop_back());
+            } else {
+                assert_eq!(iter.next_back(), v.pop_front());
+            }
+        }
+        assert_eq!(iter.next(), None);
+    }
+}
+
+#[test]
+fn test_retain() {
+    let mut beap: Beap<i32> = (0..1000).collect();
+    let capacity = beap.capacity();
+
+    beap.retain(|&x| x < 1000);
+    assert_eq!(beap.len(), 1000);
+
+    beap.retain(|&x| x % 100 == 0);
+    assert_eq!(beap.len(), 10);
+    assert_eq!(beap.capacity(), capacity);
+    assert!(beap.is_valid());
+    assert_eq!(beap.peek(), Some(&900));
+
+    beap.retain(|_| false);
+    assert!(beap.is_empty());
+    assert_eq!(beap.height, 0);
+    assert_eq!(beap.capacity(), capacity);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_retain_random() {
+    // Random tests against Vec::retain
+    let mut rng = thread_rng();
+
+    for _ in 0..100 {
+        let len = rng.gen_range(0..200);
+        let mut v: Vec<i64> = (0..len).map(|_| rng.gen_range(-100..=100)).collect();
+        let mut beap = Beap::from(v.clone());
+        let threshold = rng.gen_range(-100..=100);
+
+        beap.retain(|&x| x > threshold);
+        v.retain(|&x| x > threshold);
+        assert_pops_sorted(beap, v);
+    }
+}
+
+#[test]
+fn test_peek_mut_into_pos_mut() {
+    let mut beap = Beap::from([1, 5, 3, 4]);
+
+    // A pending sift is carried over.
+    {
+        let mut top = beap.peek_mut().unwrap();
+        *top = 0;
+        let pos = PeekMut::into_pos_mut(top);
+        assert_eq!(*pos, 0);
+    }
+    assert_eq!(beap.peek(), Some(&4));
+    assert!(beap.is_valid());
+
+    let pos = PeekMut::into_pos_mut(beap.peek_mut().unwrap());
+    assert_eq!(PosMut::remove(pos), 4);
+    assert_eq!(beap.peek(), Some(&3));
+    assert!(beap.is_valid());
+
+    // Removing the only element leaves nothing to repair.
+    let mut beap = Beap::from([7]);
+    let pos = PeekMut::into_pos_mut(beap.peek_mut().unwrap());
+    assert_eq!(PosMut::remove(pos), 7);
+    assert!(beap.is_empty());
+}
+
+#[test]
+fn test_tail_mut_into_pos_mut() {
+    let mut beap = Beap::from([1, 5, 3, 4, 2]);
+
+    // A pending repair is carried over.
+    {
+        let mut tail = beap.tail_mut().unwrap();
+        *tail = 10;
+        let pos = TailMut::into_pos_mut(tail);
+        assert_eq!(*pos, 10);
+    }
+    assert_eq!(beap.peek(), Some(&10));
+    assert_eq!(beap.tail(), Some(&2));
+    assert!(beap.is_valid());
+
+    let pos = TailMut::into_pos_mut(beap.tail_mut().unwrap());
+    assert_eq!(PosMut::remove(pos), 2);
+    assert_eq!(beap.tail(), Some(&3));
+    assert!(beap.is_valid());
+    assert_eq!(beap.into_sorted_vec(), vec![3, 4, 5, 10]);
+}
+
+#[test]
+fn test_eq_and_ord() {
+    let empty: Beap<i32> = Beap::new();
+    let beaps = [
+        empty.clone(),
+        Beap::from([1]),
+        Beap::from([2, 1, 1]),
+        Beap::from([2, 2]),
+        Beap::from([3]),
+        Beap::from([3, 0]),
+    ];
+
+    for (i, a) in beaps.iter().enumerate() {
+        for (j, b) in beaps.iter().enumerate() {
+            assert_eq!(a.cmp(b), i.cmp(&j));
+            assert_eq!(a.partial_cmp(b), Some(i.cmp(&j)));
+            assert_eq!(a == b, i == j);
+        }
+    }
+
+    let mut a = Beap::from([5, 3, 3, 1]);
+    let mut b = Beap::try_from_vec(vec![5, 3, 1, 3]).unwrap();
+    assert_ne!(a.as_slice(), b.as_slice());
+    assert_eq!(a, b);
+    assert_eq!(a.cmp(&b), std::cmp::Ordering::Equal);
+
+    a.pop();
+    b.pop_tail();
+    assert!(a < b);
+
+    let mut sorted = vec![
+        Beap::from([2, 2]),
+        Beap::from([3]),
+        empty,
+        Beap::from([2, 1, 1]),
+    ];
+    sorted.sort();
+    assert_eq!(
+        sorted,
+        [
+            Beap::new(),
+            Beap::from([1, 2, 1]),
+            Beap::from([2, 2]),
+            Beap::from([3])
+        ]
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_eq_and_ord_random() {
+    // Random tests against sorted Vecs
+    let mut rng = thread_rng();
+
+    for _ in 0..1000 {
+        let a: Vec<i64> = (0..rng.gen_range(0..6))
+            .map(|_| rng.gen_range(0..3))
+            .collect();
+        let b: Vec<i64> = (0..rng.gen_range(0..6))
+            .map(|_| rng.gen_range(0..3))
+            .collect();
+        let (beap_a, beap_b) = (Beap::from(a.clone()), Beap::from(b.clone()));
+
+        let (mut a, mut b) = (a, b);
+        a.sort_unstable_by(|x, y| y.cmp(x));
+        b.sort_unstable_by(|x, y| y.cmp(x));
+        assert_eq!(beap_a.cmp(&beap_b), a.cmp(&b));
+        assert_eq!(beap_a == beap_b, a == b);
+    }
+}
+
+#[test]
+fn test_from_iter_ref() {
+    let items = vec![4, 1, 7, 3, 3];
+    let beap: Beap<i32> = items.iter().collect();
+    assert_eq!(beap.len(), 5);
+    assert_eq!(beap.peek(), Some(&7));
+    assert!(beap.is_valid());
+    assert_eq!(beap, Beap::from(items));
+
+    let beap: Beap<i32> = [].iter().collect();
+    assert!(beap.is_empty());
+}
+
+#[test]
+fn test_reset_to() {
+    let mut beap: Beap<i32> = Beap::with_capacity(1000);
+    beap.extend(0..500);
+
+    beap.reset_to(10);
+    assert!(beap.is_empty());
+    assert_eq!(beap.height, 0);
+    assert!(beap.capacity() >= 10 && beap.capacity() < 1000);
+
+    // The capacity is not increased.
+    let mut beap: Beap<i32> = Beap::with_capacity(4);
+    beap.push(1);
+    let capacity = beap.capacity();
+    beap.reset_to(100);
+    assert!(beap.is_empty());
+    assert_eq!(beap.capacity(), capacity);
+
+    beap.push(2);
+    assert_eq!(beap.peek(), Some(&2));
+}
+
+#[test]
+fn test_from_array_same_as_vec() {
+    let arr = [7, 3, 9, 1, 5, 3, 8, 0, 6, 2, 4, 9].map(Counted);
+
+    Counted::take_comparisons();
+    let from_vec = Beap::from(arr.to_vec());
+    let vec_comparisons = Counted::take_comparisons();
+
+    let from_arr = Beap::from(arr);
+    let arr_comparisons = Counted::take_comparisons();
+
+    // The array path must not sort on its own before delegating.
+    assert_eq!(arr_comparisons, vec_comparisons);
+    assert_eq!(from_arr.as_slice(), from_vec.as_slice());
+    assert_eq!(from_arr.height, from_vec.height);
+    assert!(from_arr.is_valid());
+
+    let empty: [i32; 0] = [];
+    let beap = Beap::from(empty);
+    assert!(beap.is_empty());
+    assert_eq!(beap.height, 0);
+}
+
+#[test]
+fn test_index_greater_than_max() {
+    let beap: Beap<Counted> = (0..100).map(Counted).collect();
+    Counted::take_comparisons();
+    assert_eq!(beap.index(&Counted(100)), None);
+    assert!(!beap.contains(&Counted(1000)));
+    assert_eq!(Counted::take_comparisons(), 2);
+
+    assert_eq!(beap.index(&Counted(99)), Some(0));
+    assert!(beap.contains(&Counted(0)));
+    assert!(beap.contains(&Counted(50)));
+    assert!(!beap.contains(&Counted(-1)));
+}
+
+#[test]
+fn test_push_within_capacity() {
+    let mut beap = Beap::new();
+    assert_eq!(beap.push_within_capacity(1), Err(1));
+
+    let mut beap = Beap::with_capacity(10);
+    let capacity = beap.capacity();
+    let ptr = beap.as_slice().as_ptr();
+    for x in (0..capacity as i32).rev() {
+        assert_eq!(beap.push_within_capacity(x), Ok(()));
+        assert!(beap.is_valid());
+    }
+    assert_eq!(beap.push_within_capacity(-1), Err(-1));
+    assert_eq!(beap.push_within_capacity(100), Err(100));
+    assert_eq!(beap.capacity(), capacity);
+    assert_eq!(beap.as_slice().as_ptr(), ptr);
+
+    beap.pop();
+    assert_eq!(beap.push_within_capacity(100), Ok(()));
+    assert_eq!(beap.peek(), Some(&100));
+}
+
+#[test]
+fn test_append_many() {
+    let mut beap = Beap::from([5, -1]);
+    beap.append_many(&mut []);
+    assert_eq!(beap.len(), 2);
+
+    let mut others = [
+        Beap::from([3, 8, 1]),
+        Beap::new(),
+        Beap::from([12]),
+        Beap::from([0, 0, 7, 5]),
+        Beap::from([-4, 2]),
+    ];
+    beap.append_many(&mut others);
+
+    for other in &others {
+        assert!(other.is_empty());
+        assert_eq!(other.height, 0);
+    }
+    assert_eq!(beap.len(), 12);
+    assert_eq!(beap.peek(), Some(&12));
+    assert!(beap.is_valid());
+    assert_eq!(
+        beap.into_sorted_vec(),
+        vec![-4, -1, 0, 0, 1, 2, 3, 5, 5, 7, 8, 12]
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_append_many_random() {
+    // Random tests against a Vec collecting every input
+    let mut rng = thread_rng();
+
+    for _ in 0..100 {
+        let mut v = Vec::new();
+        let mut others: Vec<Beap<i64>> = (0..rng.gen_range(0..6))
+            .map(|_| {
+                let len = rng.gen_range(0..50);
+                let items: Vec<i64> = (0..len).map(|_| rng.gen_range(-100..=100)).collect();
+                v.extend_from_slice(&items);
+                Beap::from(items)
+            })
+            .collect();
+        let items: Vec<i64> = (0..rng.gen_range(0..50))
+            .map(|_| rng.gen_range(-100..=100))
+            .collect();
+        v.extend_from_slice(&items);
+        let mut beap = Beap::from(items);
+
+        beap.append_many(&mut others);
+        assert!(others.iter().all(Beap::is_empty));
+        assert_pops_sorted(beap, v);
+    }
+}
+
+#[test]
+fn test_remove_indices() {
+    let mut beap: Beap<i32> = Beap::new();
+    assert!(beap.remove_indices(&[0, 1]).is_empty());
+
+    let mut beap = Beap::from([1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    assert!(beap.remove_indices(&[]).is_empty());
+    assert!(beap.remove_indices(&[10, 100]).is_empty());
+    assert_eq!(beap.len(), 10);
+
+    let layout = beap.as_slice().to_vec();
+    let removed = beap.remove_indices(&[8, 0, 3, 8, 5]);
+    assert_eq!(removed, vec![layout[0], layout[3], layout[5], layout[8]]);
+    assert!(beap.is_valid());
+    assert_eq!(beap.len(), 6);
+
+    let mut rest: Vec<i32> = layout
+        .iter()
+        .filter(|x| !removed.contains(x))
+        .copied()
+        .collect();
+    rest.sort_unstable();
+    assert_eq!(beap.clone().into_sorted_vec(), rest);
+
+    let all: Vec<usize> = (0..beap.len()).rev().collect();
+    assert_eq!(beap.remove_indices(&all).len(), 6);
+    assert!(beap.is_empty());
+    assert_eq!(beap.height, 0);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_remove_indices_random() {
+    // Random tests against a Vec split by position
+    let mut rng = thread_rng();
+
+    for _ in 0..100 {
+        let len = rng.gen_range(0..200);
+        let v: Vec<i64> = (0..len).map(|_| rng.gen_range(-100..=100)).collect();
+        let mut beap = Beap::from(v);
+        let layout = beap.as_slice().to_vec();
+
+        let positions: Vec<usize> = (0..rng.gen_range(0..50))
+            .map(|_| rng.gen_range(0..len + 10))
+            .collect();
+        let removed = beap.remove_indices(&positions);
+
+        let (mut removed_expected, mut kept) = (Vec::new(), Vec::new());
+        for (i, &x) in layout.iter().enumerate() {
+            if positions.contains(&i) {
+                removed_expected.push(x);
+            } else {
+                kept.push(x);
+            }
+        }
+        assert_eq!(removed, removed_expected);
+        assert_pops_sorted(beap, kept);
+    }
+}
+
+#[test]
+fn test_summary() {
+    let mut beap = Beap::new();
+    assert_eq!(beap.summary(), None);
+
+    beap.push(4);
+    let summary = beap.summary().unwrap();
+    assert_eq!((summary.max, summary.min, summary.len), (&4, &4, 1));
+
+    beap.extend([9, -2, 7, 1, 3]);
+    let summary = beap.summary().unwrap();
+    assert_eq!(Some(summary.max), beap.peek());
+    assert_eq!(Some(summary.min), beap.tail());
+    assert_eq!(summary.len, 6);
+}
+
+#[test]
+fn test_absorb() {
+    let mut beap = Beap::from([5, -1]);
+    beap.absorb(Vec::new());
+    beap.absorb([Beap::new(), Beap::new()]);
+    assert_eq!(beap.len(), 2);
+
+    let others = vec![
+        Beap::from([3, 8, 1]),
+        Beap::new(),
+        Beap::from([12]),
+        Beap::from([0, 0, 7, 5]),
+        Beap::from([-4, 2]),
+    ];
+    beap.absorb(others);
+
+    assert_eq!(beap.len(), 12);
+    assert_eq!(beap.peek(), Some(&12));
+    assert!(beap.is_valid());
+    assert_eq!(
+        beap.into_sorted_vec(),
+        vec![-4, -1, 0, 0, 1, 2, 3, 5, 5, 7, 8, 12]
+    );
+
+    let mut beap = Beap::new();
+    beap.absorb((0..4).map(|i| Beap::from([i, 10 - i])));
+    assert_eq!(beap.into_sorted_vec(), vec![0, 1, 2, 3, 7, 8, 9, 10]);
+}
+
+#[test]
+fn test_position_of_extremes() {
+    let mut beap = Beap::new();
+    assert_eq!(beap.position_of_max(), None);
+    assert_eq!(beap.position_of_min(), None);
+
+    beap.push(4);
+    assert_eq!(beap.position_of_max(), Some(0));
+    assert_eq!(beap.position_of_min(), Some(0));
+
+    beap.extend([9, -2, 7, 1, 3]);
+    assert_eq!(beap.get(beap.position_of_max().unwrap()), Some(&9));
+    assert_eq!(beap.get(beap.position_of_min().unwrap()), Some(&-2));
+
+    let pos = beap.position_of_min().unwrap();
+    assert_eq!(beap.remove_index(pos), Some(-2));
+    assert_eq!(beap.get(beap.position_of_min().unwrap()), beap.tail());
+}
+
+#[test]
+fn test_iter_sorted() {
+    let beap: Beap<i32> = Beap::new();
+    assert_eq!(beap.iter_sorted().next(), None);
+
+    let beap = Beap::from([4, 1, 7, 3, 3, 0]);
+    let layout = beap.as_slice().to_vec();
+
+    let mut iter = beap.iter_sorted();
+    assert_eq!(iter.len(), 6);
+    assert_eq!(iter.next(), Some(&0));
+    assert_eq!(iter.next_back(), Some(&7));
+    assert_eq!(format!("{:?}", iter), "IterSorted([1, 3, 3, 4])");
+    assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &3, &3, &4]);
+
+    assert_eq!(beap.as_slice(), layout.as_slice());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_iter_sorted_random() {
+    // Random tests against a sorted Vec, iterated from both ends
+    let mut rng = thread_rng();
+
+    for _ in 0..100 {
+        let len = rng.gen_range(0..200);
+        let mut v: Vec<i64> = (0..len).map(|_| rng.gen_range(-100..=100)).collect();
+        let beap = Beap::from(v.clone());
+        let layout = beap.as_slice().to_vec();
+
+        v.sort_unstable();
+        assert!(beap.iter_sorted().eq(v.iter()));
+        assert!(beap.iter_sorted().rev().eq(v.iter().rev()));
+        assert_eq!(beap.as_slice(), layout.as_slice());
+    }
+}
+
+#[test]
+fn test_iter_rev_vs_iter_sorted_rev() {
+    let beap = Beap::from([4, 1, 7, 3, 0]);
+
+    // `iter().rev()` follows the underlying vector backwards.
+    let layout: Vec<&i32> = beap.as_slice().iter().rev().collect();
+    assert_eq!(beap.iter().rev().collect::<Vec<_>>(), layout);
+
+    // `iter_sorted().rev()` is always descending.
+    assert_eq!(
+        beap.iter_sorted().rev().collect::<Vec<_>>(),
+        vec![&7, &4, &3, &1, &0]
+    );
+
+    // The two differ as soon as the layout is not sorted descending.
+    let beap = Beap::try_from_vec(vec![5, 3, 1, 3]).unwrap();
+    assert_eq!(beap.iter().rev().collect::<Vec<_>>(), vec![&3, &1, &3, &5]);
+    assert_eq!(
+        beap.iter_sorted().rev().collect::<Vec<_>>(),
+        vec![&5, &3, &3, &1]
+    );
+}
+
+#[test]
+fn test_remove_index_reported() {
+    let mut beap = Beap::from([1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    assert_eq!(beap.remove_index_reported(10), None);
+    assert_eq!(beap.remove_index(10), None);
+    assert_eq!(beap.len(), 10);
+
+    // The last element is the minimum, so it sifts down from the hole at the top.
+    let (item, moved) = beap.remove_index_reported(0).unwrap();
+    assert_eq!(item, 10);
+    let moved = moved.unwrap();
+    assert_eq!(beap.get(moved), Some(&1));
+    assert!(beap.is_valid());
+
+    let last = beap.len() - 1;
+    let tail = beap[last];
+    assert_eq!(beap.remove_index_reported(last), Some((tail, None)));
+
+    let mut beap = Beap::from([1]);
+    assert_eq!(beap.remove_index_reported(0), Some((1, None)));
+    assert!(beap.is_empty());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_remove_index_reported_random() {
+    // Random removals checking where the last item was moved to
+    let mut rng = thread_rng();
+
+    for _ in 0..100 {
+        let len = rng.gen_range(1..200);
+        let mut v: Vec<i64> = (0..len).map(|_| rng.gen_range(-100..=100)).collect();
+        let mut beap = Beap::from(v.clone());
+
+        while !beap.is_empty() {
+            let pos = rng.gen_range(0..beap.len());
+            let last = *beap.as_slice().last().unwrap();
+            let last_pos = beap.len() - 1;
+            let (item, moved) = beap.remove_index_reported(pos).unwrap();
+
+            match moved {
+                Some(moved) => {
+                    assert_ne!(pos, last_pos);
+                    assert_eq!(beap.get(moved), Some(&last));
+                }
+                None => assert_eq!(pos, last_pos),
+            }
+            let i = v.iter().position(|&x| x == item).unwrap();
+            v.swap_remove(i);
+            assert!(beap.is_valid());
+        }
+        assert!(v.is_empty());
+    }
+}
+
+#[test]
+fn test_cached_tail_beap() {
+    let mut beap = CachedTailBeap::new();
+    assert_eq!(beap.tail(), None);
+    assert_eq!(beap.pop(), None);
+    assert_eq!(beap.pop_tail(), None);
+
+    beap.push(5);
+    assert_eq!(beap.tail(), Some(&5));
+    assert_eq!(beap.pop(), Some(5));
+    assert_eq!(beap.tail(), None);
+
+    beap.extend([4, 8, 2, 6, 2, 9]);
+    assert_eq!(beap.tail(), Some(&2));
+    assert_eq!(beap.pop_tail(), Some(2));
+    assert_eq!(beap.tail(), Some(&2));
+    assert!(beap.remove(&2));
+    assert_eq!(beap.tail(), Some(&4));
+    assert!(!beap.remove(&2));
+    beap.push(1);
+    assert_eq!(beap.tail(), Some(&1));
+    assert_eq!(beap.pop(), Some(9));
+    assert_eq!(beap.tail(), Some(&1));
+    assert_eq!(beap.into_sorted_vec(), vec![1, 4, 6, 8]);
+
+    let beap = CachedTailBeap::from(vec![3, 7, 0]);
+    assert_eq!(beap.tail(), Some(&0));
+    assert_eq!(beap.peek(), Some(&7));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_cached_tail_beap_random() {
+    // Random tests against Beap::tail
+    let mut rng = thread_rng();
+
+    for _ in 0..50 {
+        let len = rng.gen_range(0..50);
+        let mut beap: CachedTailBeap<i64> = (0..len).map(|_| rng.gen_range(-100..=100)).collect();
+
+        for _ in 0..500 {
+            match rng.gen_range(0..5) {
+                0 | 1 => beap.push(rng.gen_range(-100..=100)),
+                2 => {
+                    beap.pop();
+                }
+                3 => {
+                    beap.pop_tail();
+                }
+                _ => {
+                    beap.remove(&rng.gen_range(-100..=100));
+                }
+            }
+            assert_eq!(beap.tail(), beap.clone().into_inner().tail());
+        }
+    }
+}
+
+#[test]
+fn test_into_sorted_vec_desc() {
+    let beap: Beap<i32> = Beap::new();
+    assert!(beap.into_sorted_vec_desc().is_empty());
+
+    let mut beap = Beap::with_capacity(100);
+    beap.extend([3, 9, 1, 4, 4, 0, 7]);
+    let capacity = beap.capacity();
+    let ptr = beap.as_slice().as_ptr();
+
+    let mut expected = beap.clone().into_sorted_vec();
+    expected.reverse();
+    let vec = beap.into_sorted_vec_desc();
+    assert_eq!(vec, expected);
+    assert_eq!(vec.capacity(), capacity);
+    assert_eq!(vec.as_ptr(), ptr);
+}
+
+#[test]
+fn test_peek_mut_replace() {
+    let mut beap = Beap::from([2, 8, 5, 3]);
+
+    assert_eq!(PeekMut::replace(beap.peek_mut().unwrap(), 1), 8);
+    assert_eq!(beap.peek(), Some(&5));
+    assert!(beap.is_valid());
+
+    assert_eq!(PeekMut::replace(beap.peek_mut().unwrap(), 7), 5);
+    assert_eq!(beap.peek(), Some(&7));
+    assert!(beap.is_valid());
+    assert_eq!(beap.into_sorted_vec(), vec![1, 2, 3, 7]);
+}
+
+#[test]
+fn test_tail_mut_replace() {
+    let mut beap = Beap::from([2, 8, 5, 3]);
+
+    assert_eq!(TailMut::replace(beap.tail_mut().unwrap(), 10), 2);
+    assert_eq!(beap.peek(), Some(&10));
+    assert_eq!(beap.tail(), Some(&3));
+    assert!(beap.is_valid());
+
+    assert_eq!(TailMut::replace(beap.tail_mut().unwrap(), 0), 3);
+    assert_eq!(beap.tail(), Some(&0));
+    assert!(beap.is_valid());
+    assert_eq!(beap.into_sorted_vec(), vec![0, 5, 8, 10]);
+}
+
+#[test]
+fn test_drain_sorted() {
+    let mut beap = Beap::from([3, 1, 4, 1, 5, 9, 2]);
+    {
+        let mut drain = beap.drain_sorted();
+        assert_eq!(drain.len(), 7);
+        assert_eq!(drain.next(), Some(9));
+        assert_eq!(drain.next_back(), Some(1));
+        assert_eq!(drain.next_back(), Some(1));
+        assert_eq!(drain.next(), Some(5));
+        assert_eq!(drain.len(), 3);
+    }
+    // The rest is dropped with the iterator.
+    assert!(beap.is_empty());
+    assert_eq!(beap.height, 0);
+
+    beap.extend([2, 7, 4]);
+    let drained: Vec<_> = beap.drain_sorted().rev().collect();
+    assert_eq!(drained, vec![2, 4, 7]);
+    assert!(beap.is_empty());
+    assert_eq!(beap.height, 0);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_drain_sorted_random() {
+    // Random tests against a sorted VecDeque, drained from both ends
+    let mut rng = thread_rng();
+
+    for _ in 0..100 {
+        let len = rng.gen_range(0..200);
+        let mut v: Vec<i64> = (0..len).map(|_| rng.gen_range(-100..=100)).collect();
+        let mut beap = Beap::from(v.clone());
+        v.sort_unstable();
+        let mut v = std::collections::VecDeque::from(v);
+
+        let mut drain = beap.drain_sorted();
+        for _ in 0..rng.gen_range(0..=len) {
+            if rng.gen_bool(0.5) {
+                assert_eq!(drain.next(), v.pop_back());
+            } else {
+                assert_eq!(drain.next_back(), v.pop_front());
+            }
+            assert_eq!(drain.len(), v.len());
+        }
+        drop(drain);
+        assert!(beap.is_empty());
+        assert_eq!(beap.height, 0);
+    }
+}
+
+#[test]
+fn test_lookup_by_borrowed_form() {
+    let mut beap: Beap<String> = ["pear", "apple", "fig", "kiwi", "plum"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+    assert!(beap.contains("fig"));
+    assert!(!beap.contains("banana"));
+    let pos = beap.index("kiwi").unwrap();
+    assert_eq!(beap[pos], "kiwi");
+    assert_eq!(beap.index("zucchini"), None);
+
+    assert!(beap.remove("apple"));
+    assert!(!beap.remove("apple"));
+    assert_eq!(beap.len(), 4);
+    assert!(beap.is_valid());
+
+    // Owned values still work.
+    assert!(beap.contains(&String::from("plum")));
+}
+
+#[test]
+fn test_peek_or_insert_with() {
+    let mut beap = Beap::new();
+    assert_eq!(beap.peek_or_insert_with(|| 4), &4);
+    assert_eq!(beap.len(), 1);
+    assert_eq!(beap.height, 1);
+
+    // The closure is not called for a non-empty beap.
+    let mut beap = Beap::from([1, 7, 3]);
+    assert_eq!(beap.peek_or_insert_with(|| unreachable!()), &7);
+    assert_eq!(beap.len(), 3);
+}
+
+#[test]
+fn test_into_split_sorted() {
+    let beap: Beap<i32> = Beap::new();
+    assert_eq!(beap.into_split_sorted(&0), (vec![], vec![]));
+
+    let beap = Beap::from([4, 2, 6, 2, 8]);
+    assert_eq!(
+        beap.clone().into_split_sorted(&2),
+        (vec![2, 2], vec![8, 6, 4])
+    );
+    assert_eq!(
+        beap.clone().into_split_sorted(&1),
+        (vec![], vec![8, 6, 4, 2, 2])
+    );
+    assert_eq!(
+        beap.clone().into_split_sorted(&8),
+        (vec![2, 2, 4, 6, 8], vec![])
+    );
+    assert_eq!(beap.into_split_sorted(&5), (vec![2, 2, 4], vec![8, 6]));
+}
+
+#[test]
+fn test_append_sorted_desc_vec() {
+    let mut beap = Beap::from([4, 1, 7]);
+    beap.append_sorted_desc_vec(&mut vec![]);
+    assert_eq!(beap.len(), 3);
+
+    let mut v = vec![9, 7, 3, 0];
+    beap.append_sorted_desc_vec(&mut v);
+    assert!(v.is_empty());
+    assert_eq!(beap.height, 4);
+    assert!(beap.is_valid());
+    assert_eq!(beap.as_slice(), &[9, 7, 7, 4, 3, 1, 0]);
+
+    // The beap itself does not have to be sorted.
+    beap.push(8);
+    beap.append_sorted_desc_vec(&mut vec![5, 2]);
+    assert!(beap.is_valid());
+    assert_eq!(beap.into_sorted_vec(), vec![0, 1, 2, 3, 4, 5, 7, 7, 8, 9]);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "not sorted in descending order")]
+fn test_append_sorted_desc_vec_unsorted() {
+    let mut beap = Beap::from([4, 1, 7]);
+    beap.append_sorted_desc_vec(&mut vec![1, 2]);
+}
+
+#[test]
+fn test_append_sorted_desc_vec_keeps_capacity() {
+    let mut beap = Beap::with_capacity(16);
+    beap.extend([4, 1, 7]);
+    let capacity = beap.capacity();
+
+    beap.append_sorted_desc_vec(&mut vec![9, 3, 0]);
+    assert_eq!(beap.capacity(), capacity);
+    assert!(beap.is_valid());
+
+    let mut v = vec![String::from("c"), String::from("b")];
+    let mut beap = Beap::with_capacity(8);
+    beap.extend([String::from("d"), String::from("a")]);
+    beap.append_sorted_desc_vec(&mut v);
+    assert!(v.is_empty());
+    assert_eq!(beap.capacity(), 8);
+    assert_eq!(beap.into_sorted_vec(), ["a", "b", "c", "d"]);
+}
+
+#[test]
+fn test_append_sorted_desc_vec_panic() {
+    /// Item whose comparison panics if the other one comes from a different side.
+    #[derive(Debug, PartialEq, Eq)]
+    struct Side(i32, bool);
+
+    impl PartialOrd for Side {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Side {
+        fn cmp(&self, other: &Self) -> Ordering {
+            assert_eq!(self.1, other.1, "compared across sides");
+            self.0.cmp(&other.0)
+        }
+    }
+
+    // The merge itself is the first place where items of both sides are compared.
+    let mut beap = Beap::from([5, 3, 1].map(|x| Side(x, false)));
+    let mut v = vec![Side(4, true), Side(0, true)];
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        beap.append_sorted_desc_vec(&mut v);
+    }));
+    assert!(result.is_err());
+    assert!(v.is_empty());
+    assert_eq!(beap.len(), 5);
+    assert_eq!(beap.height, crate::height_for(5));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_append_sorted_desc_vec_random() {
+    // Random tests against append_vec
+    let mut rng = thread_rng();
+
+    for _ in 0..100 {
+        let v: Vec<i64> = (0..rng.gen_range(0..100))
+            .map(|_| rng.gen_range(-50..=50))
+            .collect();
+        let mut other: Vec<i64> = (0..rng.gen_range(0..100))
+            .map(|_| rng.gen_range(-50..=50))
+            .collect();
+        other.sort_unstable_by(|x, y| y.cmp(x));
+
+        let mut beap1 = Beap::from(v.clone());
+        let mut beap2 = Beap::from(v);
+        beap1.append_vec(&mut other.clone());
+        beap2.append_sorted_desc_vec(&mut other);
+        assert!(beap2.is_valid());
+        assert_eq!(beap1.height, beap2.height);
+
+        while let Some(x) = beap1.pop() {
+            assert_eq!(beap2.pop(), Some(x));
+        }
+        assert!(beap2.is_empty());
+    }
+}
+
+#[test]
+fn test_extract_retain() {
+    let mut beap: Beap<i32> = (0..20).collect();
+    assert!(beap.extract_retain(|_| true).is_empty());
+    assert_eq!(beap.len(), 20);
+
+    let mut removed = beap.extract_retain(|&x| x % 3 == 0);
+    removed.sort_unstable();
+    assert_eq!(removed, vec![1, 2, 4, 5, 7, 8, 10, 11, 13, 14, 16, 17, 19]);
+    assert!(beap.is_valid());
+    assert_eq!(beap.clone().into_sorted_vec(), vec![0, 3, 6, 9, 12, 15, 18]);
+
+    assert_eq!(beap.extract_retain(|_| false).len(), 7);
+    assert!(beap.is_empty());
+    assert_eq!(beap.height, 0);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_extract_retain_random() {
+    // Random tests against Iterator::partition
+    let mut rng = thread_rng();
+
+    for _ in 0..100 {
+        let len = rng.gen_range(0..200);
+        let v: Vec<i64> = (0..len).map(|_| rng.gen_range(-100..=100)).collect();
+        let mut beap = Beap::from(v.clone());
+        let threshold = rng.gen_range(-100..=100);
+
+        let mut removed = beap.extract_retain(|&x| x > threshold);
+        let (kept_expected, mut removed_expected): (Vec<i64>, Vec<i64>) =
+            v.into_iter().partition(|&x| x > threshold);
+
+        removed.sort_unstable();
+        removed_expected.sort_unstable();
+        assert_eq!(removed, removed_expected);
+        assert_pops_sorted(beap, kept_expected);
+    }
+}